@@ -10,12 +10,12 @@ use crate::dlgo::gotypes::{DisplayPoint};
 
 pub fn print_board(game: &Game) {
     let mut scr = Console::new(
-        game.get_size().try_into().unwrap(),
-        game.get_size().try_into().unwrap(),
+        game.num_cols().try_into().unwrap(),
+        game.num_rows().try_into().unwrap(),
     );
 
-    for row in 1..=game.get_size() {
-        for col in 1..=game.get_size() {
+    for row in 1..=game.num_rows() {
+        for col in 1..=game.num_cols() {
             match game.get_display_point(row, col) {
                 DisplayPoint::Empty => {
                     scr.set_pxl(
@@ -41,6 +41,8 @@ pub fn print_board(game: &Game) {
 
                 DisplayPoint::BlackStone => {}
                 DisplayPoint::WhiteStone => {}
+                DisplayPoint::DeadBlackStone => {}
+                DisplayPoint::DeadWhiteStone => {}
             }
         }
     }
@@ -13,14 +13,15 @@ pub enum ScreenType {
 
 pub fn launch(
     screen_type: ScreenType,
-    size: usize,
+    num_rows: usize,
+    num_cols: usize,
     receiver: Receiver<DisplayState>,
     resources_path: &str,
     title: &str
 ) -> Result<(), String> {
     match screen_type {
         ScreenType::Tetra => {
-            match Window::new(size, receiver, resources_path, title) {
+            match Window::new(num_rows, num_cols, receiver, resources_path, title) {
                 Ok(_) => { Ok(()) }
                 Err(err) => { Err(err.to_string()) }
             }
@@ -18,11 +18,17 @@ const MESH_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
 /// Цвет текста.
 const TEXT_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
 
-/// Обозначение ячеек по горизонтали.
-const ROW_SYMBOLS: [&'static str; 19]  = ["A", "B", "C", "D", "E", "F", "G", "H", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S", "T"];
-
-/// Обозначение ячеек по вертикали.
-const COL_NUMBERS: [&'static str; 19] = ["1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15", "16", "17", "18", "19"];
+/// Обозначение ячеек по горизонтали (буквы без I, как принято в Go; до 25 колонок).
+const ROW_SYMBOLS: [&'static str; 25] = [
+    "A", "B", "C", "D", "E", "F", "G", "H", "J", "K", "L", "M", "N",
+    "O", "P", "Q", "R", "S", "T", "U", "V", "W", "X", "Y", "Z",
+];
+
+/// Обозначение ячеек по вертикали (до 25 строк).
+const COL_NUMBERS: [&'static str; 25] = [
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13",
+    "14", "15", "16", "17", "18", "19", "20", "21", "22", "23", "24", "25",
+];
 
 /// Служебные размеры доски (для вывода информации).
 const SERVICE_WITH: usize = 600;
@@ -72,20 +78,26 @@ impl Window {
     ///
     /// # Arguments
     ///
-    /// * `size`: Размер доски в ячейках.
+    /// * `num_rows`: Число строк доски (не более 25).
+    /// * `num_cols`: Число колонок доски (не более 25).
     /// * `receiver`: Канал для получения данных.
     /// * `asset_path`: Путь к ресурсным файлам.
     /// * `title`: Заглавие окна программы.
     ///
     /// Returns: Result<(), String>
     pub(crate) fn new(
-        size: usize,
+        num_rows: usize,
+        num_cols: usize,
         receiver: Receiver<DisplayState>,
         resources_path: &str,
         title: &str
     ) -> Result<(), String> {
-        let width = size * CELL_SIZE + SERVICE_WITH;
-        let height = size * CELL_SIZE + SERVICE_HEIGHT;
+        if num_rows > COL_NUMBERS.len() || num_cols > ROW_SYMBOLS.len() {
+            return Err(format!("Доска {}x{} слишком велика для отображения", num_rows, num_cols));
+        }
+
+        let width = num_cols * CELL_SIZE + SERVICE_WITH;
+        let height = num_rows * CELL_SIZE + SERVICE_HEIGHT;
 
         // Пробуем создать контекст.
         let ctx_result = ContextBuilder::new(title, width as i32, height as i32)
@@ -129,7 +141,7 @@ impl Window {
             let mut col_text: Vec<Text> = vec![];
             let mut row_text: Vec<Text> = vec![];
             
-            for i in 0..19 {
+            for i in 0..num_rows {
                 let vector_text = Text::new(
                     COL_NUMBERS[i],
                     Font::vector(ctx, path.clone(), 32.0)?,
@@ -138,7 +150,7 @@ impl Window {
                 col_text.push(vector_text);
             }
 
-            for i in 0..19 {
+            for i in 0..num_cols {
                 let vector_text = Text::new(
                     ROW_SYMBOLS[i],
                     Font::vector(ctx, path.clone(), 32.0)?,
@@ -152,7 +164,7 @@ impl Window {
                 background_texture,
                 black_stone_texture,
                 display_state: vec![],
-                greed: Self::create_greed(ctx, 19_usize).unwrap(),
+                greed: Self::create_greed(ctx, num_rows, num_cols).unwrap(),
                 row_text,
                 col_text,
             })
@@ -164,14 +176,17 @@ impl Window {
         }
     }
 
-    fn create_greed(ctx: &mut Context, size: usize) -> tetra::Result<Mesh> {
+    fn create_greed(ctx: &mut Context, num_rows: usize, num_cols: usize) -> tetra::Result<Mesh> {
         let stroke_width = 1.0_f32;
 
+        let width = (num_cols * CELL_SIZE) as f32;
+        let height = (num_rows * CELL_SIZE) as f32;
+
         let lines = vec![
             Vec2::new(0.0, 0.0),
-            Vec2::new(0.0, (size * CELL_SIZE) as f32),
-            Vec2::new((size * CELL_SIZE) as f32, (size * CELL_SIZE) as f32),
-            Vec2::new((size * CELL_SIZE) as f32, 0.0),
+            Vec2::new(0.0, height),
+            Vec2::new(width, height),
+            Vec2::new(width, 0.0),
             Vec2::new(0.0, 0.0),
         ];
 
@@ -183,15 +198,19 @@ impl Window {
         complex.set_color(Color::rgb(0.1, 0.1, 0.1));
         complex.polyline(stroke_width, &lines)?;
 
-        for i in 1..size {
+        // Горизонтальные линии.
+        for i in 1..num_rows {
             complex.polyline(stroke_width, &[
                 Vec2::new(0.0, (i * CELL_SIZE) as f32),
-                Vec2::new((size * CELL_SIZE) as f32, (i * CELL_SIZE) as f32),
+                Vec2::new(width, (i * CELL_SIZE) as f32),
             ])?;
+        }
 
+        // Вертикальные линии.
+        for i in 1..num_cols {
             complex.polyline(stroke_width, &[
                 Vec2::new((i * CELL_SIZE) as f32, 0.0),
-                Vec2::new((i * CELL_SIZE) as f32, (size * CELL_SIZE) as f32),
+                Vec2::new((i * CELL_SIZE) as f32, height),
             ])?;
         }
 
@@ -0,0 +1,257 @@
+//! Алгоритм Бенсона: поиск безусловно живых цепочек камней.
+//!
+//! Цепочка безусловно жива, если противник не может ее захватить, даже делая ходы подряд,
+//! пока игрок пропускает ходы. Для цвета игрока доска делится на цепочки этого цвета и
+//! области - связные множества точек, не занятых камнями игрока (пустых или занятых
+//! камнями противника). Область жизненно важна для цепочки, если каждая пустая точка области
+//! является степенью свободы цепочки. Затем до стабилизации повторяются два шага:
+//!
+//! 1. Исключаются цепочки, у которых меньше двух жизненно важных "здоровых" областей.
+//! 2. Исключаются области, граничащие с исключенными цепочками.
+//!
+//! Оставшиеся цепочки безусловно живы, а их жизненно важные области принадлежат игроку.
+
+#![allow(dead_code)]
+
+use std::collections::{HashMap, HashSet};
+use crate::dlgo::board::goboard::Board;
+use crate::dlgo::board::gostring::GoString;
+use crate::dlgo::gotypes::{Color, Point};
+
+/// Результат алгоритма Бенсона: безусловно живые цепочки и принадлежащие им области.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct UnconditionalLife {
+    strings: Vec<GoString>,
+    regions: Vec<HashSet<Point>>,
+}
+
+impl UnconditionalLife {
+    /// Безусловно живые цепочки камней.
+    pub fn strings(&self) -> &[GoString] {
+        &self.strings
+    }
+
+    /// Области (глаза), жизненно важные для безусловно живых цепочек.
+    pub fn regions(&self) -> &[HashSet<Point>] {
+        &self.regions
+    }
+
+    /// Показывает, принадлежит ли камень в точке безусловно живой цепочке.
+    pub fn is_alive(&self, point: &Point) -> bool {
+        self.strings.iter().any(|string| string.get_stones2().contains(point))
+    }
+
+    /// Показывает, входит ли точка в область, принадлежащую безусловно живым цепочкам.
+    pub fn owns(&self, point: &Point) -> bool {
+        self.regions.iter().any(|region| region.contains(point))
+    }
+}
+
+/// Область для цвета игрока: точки, не занятые камнями игрока.
+struct Region {
+    points: HashSet<Point>,
+    // Цепочки игрока, граничащие с областью.
+    borders: HashSet<usize>,
+    // Цепочки, для которых область жизненно важна.
+    vital_to: HashSet<usize>,
+}
+
+/// Находит безусловно живые цепочки камней игрока алгоритмом Бенсона.
+///
+/// # Arguments
+///
+/// * `board`: Доска.
+/// * `color`: Цвет игрока.
+///
+/// Returns: UnconditionalLife
+pub fn unconditional_life(board: &Board, color: Color) -> UnconditionalLife {
+    // Цепочки игрока и принадлежность точек цепочкам.
+    let mut strings: Vec<GoString> = Vec::new();
+    let mut string_at: HashMap<Point, usize> = HashMap::new();
+    // Области: связные множества точек, не занятых камнями игрока.
+    let mut regions: Vec<Region> = Vec::new();
+    let mut in_region: HashSet<Point> = HashSet::new();
+
+    for row in 1..=board.num_rows() {
+        for col in 1..=board.num_cols() {
+            let point = Point::new(row, col);
+
+            if board.get_color(&point) == Some(color) {
+                if !string_at.contains_key(&point) {
+                    let string = board.get_go_string(&point).unwrap();
+                    for stone in string.get_stones() {
+                        string_at.insert(stone, strings.len());
+                    }
+                    strings.push(string);
+                }
+            } else if !in_region.contains(&point) {
+                let points = collect_region(board, color, point);
+                in_region.extend(points.iter().copied());
+                regions.push(Region {
+                    points,
+                    borders: HashSet::new(),
+                    vital_to: HashSet::new(),
+                });
+            }
+        }
+    }
+
+    for region in regions.iter_mut() {
+        // Цепочки, соседние каждой пустой точке области.
+        let mut empty_neighbors: Vec<HashSet<usize>> = Vec::new();
+
+        for point in &region.points {
+            let adjacent: HashSet<usize> = board.geometry().neighbors(point).iter()
+                .filter_map(|neighbor| string_at.get(neighbor).copied())
+                .collect();
+
+            region.borders.extend(adjacent.iter().copied());
+            if board.get_color(point).is_none() {
+                empty_neighbors.push(adjacent);
+            }
+        }
+
+        region.vital_to = region.borders.iter()
+            .copied()
+            .filter(|string| empty_neighbors.iter().all(|adjacent| adjacent.contains(string)))
+            .collect();
+    }
+
+    let mut alive = vec![true; strings.len()];
+    let mut healthy = vec![true; regions.len()];
+
+    loop {
+        let mut changed = false;
+
+        // Шаг 1: цепочке нужны как минимум две жизненно важные здоровые области.
+        for (string, is_alive) in alive.iter_mut().enumerate() {
+            if !*is_alive {
+                continue;
+            }
+
+            let vital = regions.iter()
+                .zip(healthy.iter())
+                .filter(|(region, &is_healthy)| is_healthy && region.vital_to.contains(&string))
+                .count();
+
+            if vital < 2 {
+                *is_alive = false;
+                changed = true;
+            }
+        }
+
+        // Шаг 2: область остается здоровой, только если все соседние цепочки живы.
+        for (region, is_healthy) in regions.iter().zip(healthy.iter_mut()) {
+            if *is_healthy && region.borders.iter().any(|&string| !alive[string]) {
+                *is_healthy = false;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let alive_regions = regions.into_iter()
+        .zip(healthy)
+        .filter(|(region, is_healthy)| {
+            *is_healthy && region.vital_to.iter().any(|&string| alive[string])
+        })
+        .map(|(region, _)| region.points)
+        .collect();
+
+    let alive_strings = strings.into_iter()
+        .zip(alive)
+        .filter(|(_, is_alive)| *is_alive)
+        .map(|(string, _)| string)
+        .collect();
+
+    UnconditionalLife {
+        strings: alive_strings,
+        regions: alive_regions,
+    }
+}
+
+/// Заливкой собирает область: связное множество точек, не занятых камнями игрока.
+fn collect_region(board: &Board, color: Color, start: Point) -> HashSet<Point> {
+    let mut region: HashSet<Point> = HashSet::new();
+    let mut stack = vec![start];
+
+    while let Some(point) = stack.pop() {
+        if !region.insert(point) {
+            continue;
+        }
+
+        for &neighbor in board.geometry().neighbors(&point) {
+            if board.get_color(&neighbor) != Some(color) && !region.contains(&neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    region
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(board: &mut Board, color: Color, points: &[(usize, usize)]) {
+        for &(row, col) in points {
+            board.place_stone((color, Point::new(row, col))).unwrap();
+        }
+    }
+
+    /// Цепочка с двумя глазами безусловно жива, а глаза принадлежат ей.
+    #[test]
+    fn two_eyes_are_unconditionally_alive() {
+        let mut board = Board::new(9, 9);
+        place(&mut board, Color::Black, &[(1, 2), (2, 1), (2, 2), (2, 3), (1, 4), (2, 4)]);
+        place(&mut board, Color::Black, &[(7, 7)]);
+        place(&mut board, Color::White, &[(3, 3)]);
+
+        let life = unconditional_life(&board, Color::Black);
+
+        assert_eq!(life.strings().len(), 1);
+        assert!(life.is_alive(&Point::new(2, 2)));
+        assert!(!life.is_alive(&Point::new(7, 7)));
+        assert_eq!(life.regions().len(), 2);
+        assert!(life.owns(&Point::new(1, 1)) && life.owns(&Point::new(1, 3)));
+        assert!(!life.owns(&Point::new(5, 5)));
+
+        // У белых безусловно живых цепочек нет.
+        assert!(unconditional_life(&board, Color::White).strings().is_empty());
+    }
+
+    /// Цепочка с одним глазом может быть захвачена.
+    #[test]
+    fn single_eye_is_not_enough() {
+        let mut board = Board::new(9, 9);
+        place(&mut board, Color::Black, &[(1, 2), (2, 1), (2, 2), (2, 3), (1, 3)]);
+
+        let life = unconditional_life(&board, Color::Black);
+
+        assert!(life.strings().is_empty());
+        assert!(life.regions().is_empty());
+    }
+
+    /// Цепочка жива, только если живы все цепочки, окружающие ее глаза.
+    #[test]
+    fn eyes_shared_with_dead_string() {
+        // Камень (1, 2) разделяет глаз (1, 1) с камнем (2, 1), а глаз (1, 3) - со стеной
+        // (2, 3), (1, 4), (2, 4). У камня (2, 1) лишь один глаз, он исключается, а вместе с
+        // ним теряют здоровье глаз (1, 1) и, следовательно, камень (1, 2).
+        let mut board = Board::new(9, 9);
+        place(&mut board, Color::Black, &[(1, 2), (2, 1), (2, 3), (1, 4), (2, 4)]);
+
+        let life = unconditional_life(&board, Color::Black);
+        assert!(life.strings().is_empty());
+
+        // Камень (2, 2) объединяет все камни в одну цепочку с двумя глазами.
+        place(&mut board, Color::Black, &[(2, 2)]);
+        let life = board.unconditionally_alive(Color::Black);
+        assert_eq!(life.strings().len(), 1);
+    }
+}
@@ -0,0 +1,196 @@
+//! Автоматическая оценка мертвых камней в конечной позиции партии.
+//!
+//! Из конечной позиции многократно доигрываются случайные партии (playouts): игроки по
+//! очереди ходят в случайные пустые точки, не заполняя собственные настоящие глаза и не
+//! делая самоубийственных ходов, пока оба не спасуют. После каждой доигровки доска подсчитывается
+//! по площади. Цепочка считается мертвой, если в большинстве доигровок ее точки достались
+//! противнику. Общие степени свободы цепочек в секи при доигровках не заполняются, а сами
+//! цепочки в секи мертвыми не считаются.
+
+use std::collections::HashSet;
+use crate::dlgo::board::goboard::{Board, EyeKind};
+use crate::dlgo::board::scoring;
+use crate::dlgo::board::seki;
+use crate::dlgo::gotypes::{Color, Point};
+use crate::dlgo::zobrist;
+
+/// Находит мертвые цепочки камней в позиции.
+///
+/// Результат детерминирован: генератор случайных ходов инициализируется переданным
+/// значением `seed`.
+///
+/// # Arguments
+///
+/// * `board`: Доска в конечной позиции партии.
+/// * `playouts`: Число случайных доигровок.
+/// * `seed`: Начальное значение генератора случайных ходов.
+///
+/// Returns: HashSet<Point> - камни мертвых цепочек
+pub fn estimate_dead_stones(board: &Board, playouts: usize, seed: u64) -> HashSet<Point> {
+    let rows = board.num_rows();
+    let cols = board.num_cols();
+
+    // Сколько раз точка досталась черным и белым.
+    let mut owned = vec![[0usize; 2]; rows * cols];
+    let mut rng = Random::new(seed);
+    let seki = seki::find_seki(board);
+
+    for _ in 0..playouts {
+        let mut playout = board.clone();
+        play_out(&mut playout, &mut rng, seki.liberties());
+
+        let territory = scoring::evaluate_territory(&playout);
+        for row in 1..=rows {
+            for col in 1..=cols {
+                if let Some(color) = territory.owner(&Point::new(row, col)) {
+                    owned[(row - 1) * cols + (col - 1)][color as usize] += 1;
+                }
+            }
+        }
+    }
+
+    let mut dead: HashSet<Point> = HashSet::new();
+    // Цепочки в секи живы, даже если доигровки их захватывают.
+    let mut visited: HashSet<Point> = HashSet::new();
+    for string in seki.strings() {
+        visited.extend(string.get_stones());
+    }
+
+    for row in 1..=rows {
+        for col in 1..=cols {
+            let point = Point::new(row, col);
+            if visited.contains(&point) {
+                continue;
+            }
+
+            let string = match board.get_go_string(&point) {
+                Some(string) => { string }
+                None => { continue; }
+            };
+
+            // Доля доигровок, в которых точки цепочки достались противнику.
+            let stones = string.get_stones();
+            let opponent = string.get_color().other() as usize;
+            let lost: usize = stones.iter()
+                .map(|stone| owned[(stone.row - 1) * cols + (stone.col - 1)][opponent])
+                .sum();
+
+            let is_dead = 2 * lost > playouts * stones.len();
+
+            for stone in stones {
+                visited.insert(stone);
+                if is_dead {
+                    dead.insert(stone);
+                }
+            }
+        }
+    }
+
+    dead
+}
+
+/// Доигрывает партию случайными ходами до двух пасов подряд (или до предела числа ходов).
+/// Общие степени свободы цепочек в секи заполняются, только если это не самоатари.
+fn play_out(board: &mut Board, rng: &mut Random, seki_points: &HashSet<Point>) {
+    let max_moves = 3 * board.num_rows() * board.num_cols();
+    let mut player = Color::Black;
+    let mut passes = 0;
+    let mut candidates: Vec<Point> = Vec::new();
+
+    for _ in 0..max_moves {
+        candidates.clear();
+        for row in 1..=board.num_rows() {
+            for col in 1..=board.num_cols() {
+                let point = Point::new(row, col);
+                if is_candidate(board, player, point, seki_points) {
+                    candidates.push(point);
+                }
+            }
+        }
+
+        if candidates.is_empty() {
+            passes += 1;
+            if passes == 2 {
+                break;
+            }
+        } else {
+            passes = 0;
+            let point = candidates[rng.below(candidates.len())];
+            // Ход проверен: точка пуста и не является самоубийством.
+            board.place_stone((player, point)).unwrap();
+        }
+
+        player = player.other();
+    }
+}
+
+/// Показывает, может ли игрок сходить в точку при доигровке: точка пуста, не является
+/// настоящим глазом игрока, ход не самоубийство и не самоатари в секи.
+fn is_candidate(board: &Board, player: Color, point: Point, seki_points: &HashSet<Point>) -> bool {
+    if board.get_color(&point).is_some() {
+        return false;
+    }
+
+    let stone = (player, point);
+    let is_own_eye = board.is_point_an_eye(&point, player) == Some(EyeKind::True);
+    let fills_seki = seki_points.contains(&point) && board.is_self_atari(stone);
+
+    !is_own_eye && !fills_seki && !board.is_self_capture(stone)
+}
+
+/// Простой генератор псевдослучайных чисел на основе функции перемешивания SplitMix64.
+struct Random {
+    state: u64,
+}
+
+impl Random {
+    fn new(seed: u64) -> Self {
+        Random { state: seed }
+    }
+
+    /// Случайное число в диапазоне 0..bound.
+    fn below(&mut self, bound: usize) -> usize {
+        self.state = self.state.wrapping_add(1);
+
+        (zobrist::splitmix64(self.state) % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlgo::board::fixtures;
+
+    /// Одинокий камень в чужой области мертв, живые группы не отмечаются.
+    #[test]
+    fn lone_invader_is_dead() {
+        // Черная стена по 5 колонке с двумя глазами на краях, белая стена по 6 колонке
+        // и белый камень (3, 3) в черной области.
+        let mut board = Board::from_diagram(fixtures::WALLS).unwrap();
+        board.place_stone((Color::White, Point::new(3, 3))).unwrap();
+
+        let dead = estimate_dead_stones(&board, 20, 1);
+
+        assert_eq!(dead.len(), 1);
+        assert!(dead.contains(&Point::new(3, 3)));
+    }
+
+    /// Цепочки в секи не считаются мертвыми.
+    #[test]
+    fn seki_is_not_dead() {
+        let board = Board::from_diagram(fixtures::SEKI).unwrap();
+
+        assert!(estimate_dead_stones(&board, 10, 3).is_empty());
+    }
+
+    /// Оценка детерминирована при одинаковом начальном значении генератора.
+    #[test]
+    fn estimate_is_reproducible() {
+        let mut board = Board::new(9, 9);
+        board.place_stone((Color::Black, Point::new(3, 3))).unwrap();
+        board.place_stone((Color::White, Point::new(7, 7))).unwrap();
+        board.place_stone((Color::White, Point::new(3, 4))).unwrap();
+
+        assert_eq!(estimate_dead_stones(&board, 10, 7), estimate_dead_stones(&board, 10, 7));
+    }
+}
@@ -0,0 +1,76 @@
+//! Позиции, общие для тестов нескольких модулей доски.
+
+use crate::dlgo::board::game::Game;
+use crate::dlgo::board::goboard::Board;
+use crate::dlgo::gotypes::{Color, Move, Point};
+
+/// Черная стена по 5 колонке и белая по 6 колонке доски 9x9: черным принадлежат колонки
+/// 1-5, белым - колонки 6-9.
+pub(crate) const WALLS: &str = "
+    . . . . X O . . .
+    . . . . X O . . .
+    . . . . X O . . .
+    . . . . X O . . .
+    . . . . X O . . .
+    . . . . X O . . .
+    . . . . X O . . .
+    . . . . X O . . .
+    . . . . X O . . .
+";
+
+/// Секи без глаз у верхнего края доски 9x9: внутренние черная и белая цепочки разделяют
+/// степени свободы (1, 2) и (1, 3), белая цепочка окружена внешней черной стеной.
+pub(crate) const SEKI: &str = "
+    O . . O X . . . .
+    O X X O X . . . .
+    O X X O X . . . .
+    O O O O X . . . .
+    X X X X X . . . .
+    . . . . . . . . .
+    . . . . . . . . .
+    . . . . . . . . .
+    . . . . . . . . .
+";
+
+/// Секи с глазами на доске 5x5: внутренняя черная цепочка с глазом (1, 1) и белая цепочка
+/// с глазом (1, 5) разделяют степень свободы (1, 3), внешняя черная стена владеет пятым
+/// рядом.
+pub(crate) const SEKI_WITH_EYES: &str = "
+    . X . O .
+    X X X O O
+    O O O O O
+    X X X X X
+    . . . . .
+";
+
+/// Разыгрывает позицию диаграммы в партии: черные и белые камни (построчно) ставятся по
+/// очереди, начиная с черных. Если камни одного цвета закончились, его ходы заменяются
+/// пасами, поэтому диаграмма не должна зависеть от правила "камня за пас".
+pub(crate) fn play_diagram(game: &mut Game, diagram: &str) {
+    let board = Board::from_diagram(diagram).unwrap();
+
+    let stones = |color: Color| -> Vec<Point> {
+        (1..=board.num_rows())
+            .flat_map(|row| (1..=board.num_cols()).map(move |col| Point::new(row, col)))
+            .filter(|point| board.get_color(point) == Some(color))
+            .collect()
+    };
+    let mut black = stones(Color::Black).into_iter();
+    let mut white = stones(Color::White).into_iter();
+
+    loop {
+        let moves = [(Color::Black, black.next()), (Color::White, white.next())];
+        if moves.iter().all(|(_, point)| point.is_none()) {
+            break;
+        }
+
+        for (color, point) in moves {
+            let player_move = match point {
+                Some(point) => { Move::Play((color, point)) }
+                None => { Move::Pass(color) }
+            };
+
+            game.apply_move(player_move).unwrap();
+        }
+    }
+}
@@ -3,40 +3,166 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
-use std::rc::Rc;
-use crate::dlgo::error::FatalError;
+use std::collections::HashSet;
+use std::sync::Arc;
+use crate::dlgo::error::{FatalError, IllegalMove};
+use crate::dlgo::board::dead_stones;
 use crate::dlgo::board::goboard::Board;
+use crate::dlgo::board::handicap;
+use crate::dlgo::board::result::GameResult;
+use crate::dlgo::board::rules::{KoRule, RuleSet, Scoring};
+use crate::dlgo::board::scoring::{self, Territory};
+use crate::dlgo::board::seki;
 use crate::dlgo::gotypes::{Color, DisplayPoint, DisplayState, Move, Point};
 
 /// Игра в Go!
 pub struct Game {
-    // Состояние в игре неизменяемо (!, тип Rc). Это значит, что после
+    // Состояние в игре неизменяемо (!, тип Arc). Это значит, что после
     // создания состояния, новое состояние можно создать лиш клонированием
-    // старого, совершить ход и заморозить (обернуть в Rc).
-    state: Rc<GameState>,
-    board_size: usize,
+    // старого, совершить ход и заморозить (обернуть в Arc).
+    state: Arc<GameState>,
+    // Множество всех ситуаций (игрок, который должен сделать ход, и хеш Зобриста доски),
+    // встречавшихся в игре. Позволяет проверять правила суперко за O(1).
+    situations: HashSet<(Color, u64)>,
+    // Правила, по которым ведется игра.
+    rules: RuleSet,
+    // Коми: очки, добавляемые белым.
+    komi: f32,
+    // Камни, отмеченные мертвыми после окончания партии (учитываются при подсчете очков).
+    dead_stones: HashSet<Point>,
+    // Камни форы, выставленные черными перед началом партии.
+    handicap: Vec<Point>,
+    // Состояния, отмененные методом undo (последнее отмененное - в конце). Позволяют
+    // вернуть отмененные ходы методом redo; очищаются при новом ходе.
+    redo_stack: Vec<Arc<GameState>>,
+    num_rows: usize,
+    num_cols: usize,
     is_over: bool,
 }
 
 impl Game {
-    /// Конструктор. Создает новую игру.
+    /// Конструктор. Создает новую игру на квадратной доске. Паникует, если размер доски
+    /// вне диапазона от MIN_BOARD_SIZE до MAX_BOARD_SIZE (см. new_rectangular).
     ///
     /// # Arguments
     ///
     /// * `board_size`: Размер доски
+    /// * `rules`: Правила игры.
     ///
     /// Returns: GameState
-    pub fn new(board_size: usize) -> Self {
+    pub fn new(board_size: usize, rules: RuleSet) -> Self {
+        Self::new_rectangular(board_size, board_size, rules).unwrap()
+    }
+
+    /// Конструктор. Создает новую игру на прямоугольной доске размером от 2x2 до 25x25.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_rows`: Число строк доски.
+    /// * `num_cols`: Число колонок доски.
+    /// * `rules`: Правила игры.
+    ///
+    /// Returns: Result<Game, FatalError>
+    pub fn new_rectangular(num_rows: usize, num_cols: usize, rules: RuleSet) -> Result<Self, FatalError> {
+        Board::check_size(num_rows, num_cols)?;
+
+        Ok(Self::with_handicap_stones(num_rows, num_cols, rules, rules.default_komi(), Vec::new()))
+    }
+
+    /// Конструктор. Создает игру с фиксированной форой: черные камни выставляются на
+    /// стандартные пункты (в порядке протокола GTP), первыми ходят белые.
+    ///
+    /// # Arguments
+    ///
+    /// * `board_size`: Размер доски (9, 13 или 19).
+    /// * `rules`: Правила игры.
+    /// * `stones`: Число камней форы (от 2 до 9).
+    ///
+    /// Returns: Result<Game, FatalError>
+    pub fn with_fixed_handicap(board_size: usize, rules: RuleSet, stones: usize) -> Result<Self, FatalError> {
+        let points = handicap::fixed_handicap_points(board_size, stones)?;
+
+        let komi = rules.handicap_komi(points.len());
+
+        Ok(Self::with_handicap_stones(board_size, board_size, rules, komi, points))
+    }
+
+    /// Конструктор. Создает игру со свободной форой: черные камни выставляются на
+    /// выбранные пункты, первыми ходят белые.
+    ///
+    /// # Arguments
+    ///
+    /// * `board_size`: Размер доски.
+    /// * `rules`: Правила игры.
+    /// * `points`: Пункты камней форы.
+    ///
+    /// Returns: Result<Game, FatalError>
+    pub fn with_free_handicap(board_size: usize, rules: RuleSet, points: &[Point]) -> Result<Self, FatalError> {
+        Board::check_size(board_size, board_size)?;
+        handicap::validate_free_handicap(board_size, points)?;
+
+        let komi = rules.handicap_komi(points.len());
+
+        Ok(Self::with_handicap_stones(board_size, board_size, rules, komi, points.to_vec()))
+    }
+
+    /// Создает игру, в начальной позиции которой выставлены камни форы. Без форы первыми
+    /// ходят черные, с форой - белые. Камни форы являются расстановкой, а не ходами: они
+    /// входят в начальное состояние истории, но не в moves() (см. handicap()).
+    ///
+    /// # Arguments
+    ///
+    /// * `num_rows`: Число строк доски.
+    /// * `num_cols`: Число колонок доски.
+    /// * `rules`: Правила игры.
+    /// * `komi`: Коми.
+    /// * `handicap`: Пункты камней форы (различные, на доске).
+    ///
+    /// Returns: Game
+    pub(crate) fn with_handicap_stones(
+        num_rows: usize,
+        num_cols: usize,
+        rules: RuleSet,
+        komi: f32,
+        handicap: Vec<Point>,
+    ) -> Self {
+        // Пустая доска размера num_rows x num_cols.
+        let mut board = Board::new(num_rows, num_cols);
+        for point in &handicap {
+            // Пункты форы проверены: различны и лежат на доске.
+            board.place_stone((Color::Black, *point)).unwrap();
+        }
+
+        let player_color = if handicap.is_empty() {
+            // Первым в игру вступает игрок черными камнями.
+            Color::Black
+        } else {
+            Color::White
+        };
+
+        let state = GameState {
+            board,
+            player_color,
+            previous_state: None,
+            last_move: None,
+            prisoners: [0, 0],
+            captured: Vec::new(),
+            move_number: 0,
+        };
+
+        let mut situations = HashSet::new();
+        situations.insert(state.situation_hash());
+
         Game {
-            state: Rc::new(GameState {
-                // Пустая доска размера board_size x board_size.
-                board: Board::new(board_size, board_size),
-                // Первым в игру вступает игрок черными камнями.
-                player_color: Color::Black,
-                previous_state: None,
-                last_move: None,
-            }),
-            board_size,
+            state: Arc::new(state),
+            situations,
+            rules,
+            komi,
+            dead_stones: HashSet::new(),
+            handicap,
+            redo_stack: Vec::new(),
+            num_rows,
+            num_cols,
             is_over: false,
         }
     }
@@ -47,83 +173,469 @@ impl Game {
     ///
     /// * `player_move`: Ход игрока. Ход содержит цвет камней игрока.
     ///
-    /// Returns: Result<(), RecoverableError>
-    pub fn apply_move(&mut self, player_move: Move) -> Result<(), FatalError> {
+    /// Returns: Result<(), IllegalMove>
+    pub fn apply_move(&mut self, player_move: Move) -> Result<(), IllegalMove> {
         if self.is_over {
-            let err = FatalError::new("Игра завершена! Нельзя делать ходы в завершенной игре.".to_string());
-
-            return Err(err);
+            return Err(IllegalMove::GameOver);
         }
 
+        // Ход должен делать игрок своей очереди, он не должен занимать уже занятую точку,
+        // приводить к самозахвату (если правила его не допускают) или нарушать правило "ко".
+        self.state.check_move(player_move, &self.rules, &self.situations)?;
+
         self.state = match player_move {
             // Размещение камня на доске, доска изменилась.
             Move::Play(stone) => {
-                // Проверим правильность хода: цвет камня, размещаемого на доске, должен
-                // совпадать с цветом камней, которыми должен играть "следующий игрок"
-                // (хранится в поле GameState::next_player_color).
                 let color = stone.0;
-                if color != self.state.player_color {
-                    let err = FatalError::new(format!(
-                        "Размещаемый камень должен иметь {} цвет", self.state.player_color
-                    ));
-
-                    return Err(err);
-                }
 
                 // Следующее состояние доски: клонируем доску и размещаем камень.
                 let mut next_board = self.state.board.clone();
                 // Транслируем ошибку вызывающей функции.
-                next_board.place_stone(stone)?;
+                let captured = next_board.place_stone(stone)?;
+
+                // Снятые с доски камни противника становятся пленными игрока.
+                let mut prisoners = self.state.prisoners;
+                prisoners[color as usize] += captured.len();
 
                 // Создаем новое состояние в игре.
-                Rc::new(GameState {
+                Arc::new(GameState {
                     board: next_board,
                     player_color: color.other(),
                     previous_state: Some(self.state.clone()),
                     last_move: Some(player_move),
+                    prisoners,
+                    captured,
+                    move_number: self.state.move_number + 1,
                 })
             }
 
             // Пропуск хода или выход из игры, расположение камней не меняется.
             Move::Pass(color) | Move::Resign(color) => {
+                // По правилу "камня за пас" пропускающий ход игрок отдает камень противнику.
+                let mut prisoners = self.state.prisoners;
+                if let (Move::Pass(_), true) = (player_move, self.rules.pass_stones) {
+                    prisoners[color.other() as usize] += 1;
+                }
+
                 // Создаем новое состояние в игре.
-                Rc::new(GameState {
+                Arc::new(GameState {
                     board: self.state.board.clone(),
                     player_color: color.other(),
                     previous_state: Some(self.state.clone()),
                     last_move: Some(player_move),
+                    prisoners,
+                    captured: Vec::new(),
+                    move_number: self.state.move_number + 1,
                 })
             }
         };
 
+        // Новый ход отменяет возможность вернуть ранее отмененные ходы.
+        self.redo_stack.clear();
+
+        // Запоминаем новую ситуацию в игре.
+        self.situations.insert(self.state.situation_hash());
+
         // После совершенного хода - проверяем, не завершилась ли игра.
-        self.is_over = self.state.is_over();
+        self.is_over = self.state.is_over(&self.rules);
 
         Ok(())
     }
 
+    /// Отменяет последний ход. Отмененный ход можно вернуть методом redo.
+    ///
+    /// Returns: bool - false, если отменять нечего (начальная позиция)
+    pub fn undo(&mut self) -> bool {
+        if !self.step_back() {
+            return false;
+        }
+
+        self.sync_with_state();
+
+        true
+    }
+
+    /// Возвращает последний отмененный ход.
+    ///
+    /// Returns: bool - false, если отмененных ходов нет
+    pub fn redo(&mut self) -> bool {
+        if !self.step_forward() {
+            return false;
+        }
+
+        self.sync_with_state();
+
+        true
+    }
+
+    /// Переходит к позиции после хода с номером `move_number` (0 - начальная позиция).
+    /// Доступны ходы текущей ветви партии: сделанные и отмененные методом undo.
+    ///
+    /// # Arguments
+    ///
+    /// * `move_number`: Номер хода.
+    ///
+    /// Returns: Result<(), FatalError>
+    pub fn goto_move(&mut self, move_number: usize) -> Result<(), FatalError> {
+        let last = self.state.move_number + self.redo_stack.len();
+        if move_number > last {
+            let err = FatalError::new(format!(
+                "Нет хода с номером {}, в партии {} ходов", move_number, last
+            ));
+
+            return Err(err);
+        }
+
+        while self.state.move_number > move_number {
+            self.step_back();
+        }
+        while self.state.move_number < move_number {
+            self.step_forward();
+        }
+
+        self.sync_with_state();
+
+        Ok(())
+    }
+
+    /// Номер последнего сделанного хода (0 в начальной позиции).
+    pub fn move_number(&self) -> usize {
+        self.state.move_number
+    }
+
+    /// Текущее состояние игры.
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// Итератор по состояниям игры от начальной позиции до текущей.
+    pub fn history(&self) -> impl Iterator<Item = &GameState> {
+        let mut states: Vec<&GameState> = Vec::with_capacity(self.state.move_number + 1);
+
+        let mut state: Option<&GameState> = Some(&self.state);
+        while let Some(current) = state {
+            states.push(current);
+            state = current.previous_state.as_deref();
+        }
+
+        states.into_iter().rev()
+    }
+
+    /// Итератор по ходам партии от первого до последнего сделанного.
+    pub fn moves(&self) -> impl Iterator<Item = Move> + '_ {
+        self.history().filter_map(|state| state.last_move)
+    }
+
+    /// Общий указатель на текущее состояние (состояния неизменяемы и разделяются, например,
+    /// узлами дерева партии).
+    pub(crate) fn shared_state(&self) -> Arc<GameState> {
+        self.state.clone()
+    }
+
+    /// Переходит к произвольному состоянию, полученному ранее в этой игре (например, к узлу
+    /// другой ветви дерева партии). Стек отмененных ходов очищается.
+    pub(crate) fn jump_to(&mut self, state: Arc<GameState>) {
+        self.state = state;
+        self.redo_stack.clear();
+        self.sync_with_state();
+    }
+
+    /// Переходит к предыдущему состоянию, запоминая текущее для redo.
+    fn step_back(&mut self) -> bool {
+        let previous = match &self.state.previous_state {
+            Some(previous) => { previous.clone() }
+            None => { return false; }
+        };
+
+        let current = std::mem::replace(&mut self.state, previous);
+        self.redo_stack.push(current);
+
+        true
+    }
+
+    /// Переходит к последнему отмененному состоянию.
+    fn step_forward(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(next) => {
+                self.state = next;
+                true
+            }
+            None => { false }
+        }
+    }
+
+    /// Приводит служебные данные игры в соответствие с текущим состоянием после навигации
+    /// по истории: заново собирает множество ситуаций по цепочке состояний, определяет,
+    /// завершена ли игра, и сбрасывает отметки мертвых камней.
+    fn sync_with_state(&mut self) {
+        self.situations.clear();
+
+        let mut state: Option<&GameState> = Some(&self.state);
+        while let Some(current) = state {
+            self.situations.insert(current.situation_hash());
+            state = current.previous_state.as_deref();
+        }
+
+        self.is_over = self.state.is_over(&self.rules);
+        self.dead_stones.clear();
+    }
+
+    /// Возвращает все допустимые ходы игрока, чья очередь ходить: размещения камней,
+    /// не нарушающие правил самозахвата и ко, а также пропуск хода и выход из игры.
+    /// Для завершенной игры возвращается пустой вектор.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        if self.is_over {
+            return Vec::new();
+        }
+
+        let color = self.state.player_color;
+        let mut moves = Vec::with_capacity(self.num_rows * self.num_cols + 2);
+
+        for row in 1..=self.num_rows {
+            for col in 1..=self.num_cols {
+                let candidate = Move::Play((color, Point::new(row, col)));
+
+                if self.state.check_move(candidate, &self.rules, &self.situations).is_ok() {
+                    moves.push(candidate);
+                }
+            }
+        }
+
+        moves.push(Move::Pass(color));
+        moves.push(Move::Resign(color));
+
+        moves
+    }
+
+    /// Правила, по которым ведется игра.
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Коми: очки, добавляемые белым.
+    pub fn komi(&self) -> f32 {
+        self.komi
+    }
+
+    /// Устанавливает коми. По умолчанию коми определяется правилами игры
+    /// (RuleSet::default_komi).
+    ///
+    /// # Arguments
+    ///
+    /// * `komi`: Очки, добавляемые белым.
+    ///
+    /// Returns: ()
+    pub fn set_komi(&mut self, komi: f32) {
+        self.komi = komi;
+    }
+
     /// Метод определяет, завершена ли игра.
     pub fn is_over(&self) -> bool {
         self.is_over
     }
 
-    /// Отдает размер доски.
-    pub fn get_size(&self) -> usize {
-        self.board_size
+    /// Число пленных игрока: камни противника, снятые с доски, и камни, отданные
+    /// противником за пас (по правилу "камня за пас"). Камни, отмеченные мертвыми,
+    /// не учитываются.
+    ///
+    /// # Arguments
+    ///
+    /// * `color`: Цвет игрока.
+    ///
+    /// Returns: usize
+    pub fn prisoners(&self, color: Color) -> usize {
+        self.state.prisoners[color as usize]
+    }
+
+    /// Камни форы, выставленные черными перед началом партии (пусто в игре без форы).
+    /// Камни стоят уже в начальной позиции history() и не входят в moves().
+    pub fn handicap(&self) -> &[Point] {
+        &self.handicap
+    }
+
+    /// Точки камней, снятых с доски последним ходом (пусто, если ход ничего не захватил).
+    pub fn last_captures(&self) -> &[Point] {
+        &self.state.captured
+    }
+
+    /// Показывает, идет ли отметка мертвых камней: партия завершена двумя пасами подряд
+    /// (а не выходом игрока из игры).
+    pub fn is_marking(&self) -> bool {
+        self.is_over && !matches!(self.state.last_move, Some(Move::Resign(_)))
+    }
+
+    /// Проверяет, что идет отметка мертвых камней.
+    fn check_marking_phase(&self) -> Result<(), FatalError> {
+        if !self.is_marking() {
+            let err = FatalError::new(
+                "Отмечать мертвые камни можно только после завершения партии пасами".to_string()
+            );
+
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Отмечает мертвой (или снимает отметку) цепочку камней, которой принадлежит камень
+    /// в переданной точке. Доступно только во время отметки мертвых камней.
+    ///
+    /// # Arguments
+    ///
+    /// * `point`: Точка, занятая камнем цепочки.
+    ///
+    /// Returns: Result<(), FatalError>
+    pub fn toggle_dead_string(&mut self, point: &Point) -> Result<(), FatalError> {
+        self.check_marking_phase()?;
+
+        let string = match self.state.board.get_go_string(point) {
+            Some(string) => { string }
+            None => {
+                let err = FatalError::new(format!(
+                    "В точке ({}, {}) нет камня", point.row, point.col
+                ));
+
+                return Err(err);
+            }
+        };
+
+        let is_dead = self.dead_stones.contains(point);
+        for stone in string.get_stones() {
+            if is_dead {
+                self.dead_stones.remove(&stone);
+            } else {
+                self.dead_stones.insert(stone);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Показывает, отмечен ли камень в переданной точке мертвым.
+    pub fn is_dead(&self, point: &Point) -> bool {
+        self.dead_stones.contains(point)
+    }
+
+    /// Камни, отмеченные мертвыми.
+    pub fn dead_stones(&self) -> &HashSet<Point> {
+        &self.dead_stones
+    }
+
+    /// Автоматически отмечает мертвые камни по результатам случайных доигровок конечной
+    /// позиции (предыдущие отметки заменяются). Доступно только во время отметки мертвых
+    /// камней; результат можно исправить вручную методом toggle_dead_string.
+    ///
+    /// # Arguments
+    ///
+    /// * `playouts`: Число случайных доигровок.
+    ///
+    /// Returns: Result<(), FatalError>
+    pub fn estimate_dead_stones(&mut self, playouts: usize) -> Result<(), FatalError> {
+        self.check_marking_phase()?;
+
+        // Хеш позиции в качестве начального значения делает оценку воспроизводимой.
+        let seed = self.state.board.zobrist_hash();
+        self.dead_stones = dead_stones::estimate_dead_stones(&self.state.board, playouts, seed);
+
+        Ok(())
+    }
+
+    /// Возвращает результат завершенной партии, или None, если игра не окончена. При выходе
+    /// игрока из игры побеждает противник, после двух пасов подряд побеждает игрок,
+    /// набравший больше очков с учетом коми. Очки считаются по правилам игры: по площади
+    /// (камни и территория) или по территории (территория и пленные, включая мертвые
+    /// камни). Мертвые камни снимаются с доски перед подсчетом.
+    pub fn result(&self) -> Option<GameResult> {
+        if !self.is_over {
+            return None;
+        }
+
+        if let Some(Move::Resign(color)) = self.state.last_move {
+            return Some(GameResult::Resignation(color.other()));
+        }
+
+        let (black, white) = self.score();
+
+        Some(GameResult::from_margin(black - (white + self.komi)))
+    }
+
+    /// Подсчет очков черных и белых (без коми) по правилам игры с учетом мертвых камней. При
+    /// подсчете по территории глаза цепочек в секи не считаются территорией.
+    ///
+    /// Returns: (f32, f32)
+    pub fn score(&self) -> (f32, f32) {
+        let (board, dead_prisoners) = self.board_without_dead_stones();
+        let territory = scoring::evaluate_territory(&board);
+
+        match self.rules.scoring {
+            Scoring::Area => {
+                (territory.black_area() as f32, territory.white_area() as f32)
+            }
+            Scoring::Territory => {
+                let black_prisoners = self.prisoners(Color::Black) + dead_prisoners[0];
+                let white_prisoners = self.prisoners(Color::White) + dead_prisoners[1];
+
+                // Глаза цепочек в секи при подсчете по территории не засчитываются.
+                let mut seki_eyes = [0, 0];
+                for eye in seki::find_seki(&board).eyes() {
+                    if let Some(owner) = territory.owner(eye) {
+                        seki_eyes[owner as usize] += 1;
+                    }
+                }
+
+                (
+                    (territory.black_territory - seki_eyes[0] + black_prisoners) as f32,
+                    (territory.white_territory - seki_eyes[1] + white_prisoners) as f32,
+                )
+            }
+        }
+    }
+
+    /// Подсчет камней и территории в текущей позиции (с картой принадлежности точек).
+    /// Камни, отмеченные мертвыми, считаются снятыми с доски.
+    pub fn territory(&self) -> Territory {
+        scoring::evaluate_territory(&self.board_without_dead_stones().0)
+    }
+
+    /// Копия текущей доски, с которой сняты камни, отмеченные мертвыми.
+    ///
+    /// Returns: (Board, [usize; 2]) - доска и число мертвых камней, ставших пленными
+    /// черных и белых
+    fn board_without_dead_stones(&self) -> (Board, [usize; 2]) {
+        let mut board = self.state.board.clone();
+        let mut prisoners = [0, 0];
+
+        for point in &self.dead_stones {
+            if let Some(color) = board.get_color(point) {
+                prisoners[color.other() as usize] += board.remove_string_at(point);
+            }
+        }
+
+        (board, prisoners)
+    }
+
+    /// Число строк доски.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Число колонок доски.
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
     }
 
     /// Возвращает текущее отображаемое состояние точки игрового поля.
     pub fn get_display_point(&self, row: usize, col: usize) -> DisplayPoint {
         let point = Point::new(row, col);
 
-        match self.state.board.get_go_string(&point) {
+        let is_dead = self.dead_stones.contains(&point);
+
+        match self.state.board.get_color(&point) {
             None => { DisplayPoint::Empty }
-            Some(string) => {
-                match string.get_color() {
-                    Color::Black => { DisplayPoint::BlackStone }
-                    Color::White => { DisplayPoint::WhiteStone }
-                }
-            }
+            Some(Color::Black) if is_dead => { DisplayPoint::DeadBlackStone }
+            Some(Color::White) if is_dead => { DisplayPoint::DeadWhiteStone }
+            Some(Color::Black) => { DisplayPoint::BlackStone }
+            Some(Color::White) => { DisplayPoint::WhiteStone }
         }
     }
 
@@ -131,13 +643,13 @@ impl Game {
     /// на экране.
     pub fn get_display_state(&self) -> DisplayState {
         // Контейнер строк, т.е. Vec<Vec<DisplayPoint>>.
-        let mut state: DisplayState = Vec::with_capacity(self.board_size);
+        let mut state: DisplayState = Vec::with_capacity(self.num_rows);
 
         // Создаем строки и заполняем текущим состоянием игрового поля.
-        for row_idx in 1..=self.board_size {
-            let mut row: Vec<DisplayPoint> = Vec::with_capacity(self.board_size);
+        for row_idx in 1..=self.num_rows {
+            let mut row: Vec<DisplayPoint> = Vec::with_capacity(self.num_cols);
 
-            for col_idx in 1..=self.board_size {
+            for col_idx in 1..=self.num_cols {
                 row.push(self.get_display_point(row_idx, col_idx));
             }
             // Помещаем заполненную строку в контейнер строк.
@@ -149,37 +661,109 @@ impl Game {
 }
 
 /// Структура хранящая состояние игры.
-struct GameState {
+pub struct GameState {
     board: Board,                      // Текущее состояние доски (к этому состоянию ожидается
                                        // ход цветом, который хранится в поле player_color).
     player_color: Color,               // Цвет игрока (камня), который должен сделать ход.
-    previous_state: Option<Rc<Self>>,  // Предыдущее состояние доски (до "последнего" хода).
+    previous_state: Option<Arc<Self>>,  // Предыдущее состояние доски (до "последнего" хода).
     last_move: Option<Move>,           // Последний ход (ход который перевел доску в текущее
                                        // состояние).
+    prisoners: [usize; 2],             // Пленные черных и белых (индекс - цвет игрока).
+    captured: Vec<Point>,              // Камни, снятые с доски последним ходом.
+    move_number: usize,                // Номер последнего хода (0 в начальной позиции).
 }
 
 impl GameState {
+    /// Доска в этом состоянии.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Цвет игрока, который должен сделать ход.
+    pub fn player_color(&self) -> Color {
+        self.player_color
+    }
+
+    /// Ход, который привел к этому состоянию (None для начальной позиции).
+    pub fn last_move(&self) -> Option<Move> {
+        self.last_move
+    }
+
+    /// Предыдущее состояние (None для начальной позиции).
+    pub fn previous_state(&self) -> Option<&GameState> {
+        self.previous_state.as_deref()
+    }
+
+    /// Номер последнего хода (0 в начальной позиции).
+    pub fn move_number(&self) -> usize {
+        self.move_number
+    }
+
+    /// Число пленных игрока к этому состоянию.
+    pub fn prisoners(&self, color: Color) -> usize {
+        self.prisoners[color as usize]
+    }
+
+    /// Точки камней противника, снятых с доски ходом, который привел к этому состоянию.
+    pub fn captured(&self) -> &[Point] {
+        &self.captured
+    }
+
     /// Вспомогательный метод, отдающий "ситуацию" в игре, т.е. кортеж из игрока (цвета),
     /// который должен сделать ход и состояния доски.
     fn situation(&self) -> (&Color, &Board) {
         (&self.player_color, &self.board)
     }
 
+    /// Хешированная "ситуация" в игре: игрок, который должен сделать ход, и хеш Зобриста доски.
+    fn situation_hash(&self) -> (Color, u64) {
+        (self.player_color, self.board.zobrist_hash())
+    }
+
+    /// Вычисляет хеш Зобриста доски, которая получится после хода игрока. Возвращает None,
+    /// если ход не размещает камень на доске или камень разместить нельзя.
+    ///
+    /// # Arguments
+    ///
+    /// * `player`: Игрок делающий ход.
+    /// * `player_move`: Ход игрока.
+    ///
+    /// Returns: Option<u64>
+    fn next_board_hash(&self, player: Color, player_move: Move) -> Option<u64> {
+        match player_move {
+            Move::Play((_, point)) => {
+                if !self.board.is_on_grid(&point) || self.board.get_color(&point).is_some() {
+                    return None;
+                }
+
+                Some(self.board.hash_after_move((player, point)))
+            }
+            _ => { None }
+        }
+    }
+
     /// Определение момента окончания игры по последнему ходу, т.е. по "ходу" который перевел
     /// игровую доску в текущее состояние.
     /// Игра оканчивается в случае: игрок выходит из игры (текущий ход Move::Resign), игроки
-    /// подряд пропускают ход (Move::Pass).
-    fn is_over(&self) -> bool {
+    /// подряд пропускают ход (Move::Pass). По правилу "камня за пас" последний пас должны
+    /// делать белые.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules`: Правила игры.
+    ///
+    /// Returns: bool
+    fn is_over(&self, rules: &RuleSet) -> bool {
         if let Some(last_move) = &self.last_move {
             // Что это за ход?
             match last_move {
                 // Это пропуск хода?
-                Move::Pass(_) => {
+                Move::Pass(color) => {
                     match &self.previous_state {
                         Some(previous_last_state) => {
                             // Это второй "пас", игра завершается.
                             if let Some(Move::Pass(_)) = previous_last_state.last_move {
-                                return true;
+                                return !rules.pass_stones || *color == Color::White;
                             }
                         }
                         // Если предыдущего состояния не было, это первый пас.
@@ -207,72 +791,612 @@ impl GameState {
     ///
     /// Returns: bool
     fn is_move_self_capture(&self, player: Color, player_move: Move) -> bool {
-
-
-        /*
-        if not move.is_play:
-            return False
-        next_board = copy.deepcopy(self.board)
-        next_board.place_stone(player, move.point)
-        new_string = next_board.get_go_string(move.point)
-
-        return new_string.num_liberties == 0
-
-        */
-        todo!()
+        match player_move {
+            // Доска проверяет ход без размещения камня (и без клонирования доски).
+            Move::Play((_, point)) => {
+                self.board.is_on_grid(&point)
+                    && self.board.get_color(&point).is_none()
+                    && self.board.is_self_capture((player, point))
+            }
+            // Пропуск хода и выход из игры не размещают камней на доске.
+            _ => { false }
+        }
     }
 
-    /// Метод определяет, нарушит ли ход игрока правило "ко" (ситуационное суперко).
+    /// Метод определяет, нарушит ли ход игрока правило "ко" в варианте, заданном правилами
+    /// игры.
     ///
     /// # Arguments
     ///
     /// * `player`: Игрок делающий ход.
     /// * `player_move`: Ход игрока.
+    /// * `ko_rule`: Правило, запрещающее повторение позиций.
+    /// * `past_situations`: Ситуации, встречавшиеся в игре.
     ///
     /// Returns: bool
-    fn does_move_violate_ko(&self, player: Color, player_move: Move) -> bool {
-
-
-        /*
-        if not move.is_play:
-            return False
-
-        next_board = copy.deepcopy(self.board)
-        next_board.place_stone(player, move.point)
-        next_situation = (player.other, next_board)
+    fn does_move_violate_ko(
+        &self,
+        player: Color,
+        player_move: Move,
+        ko_rule: KoRule,
+        past_situations: &HashSet<(Color, u64)>
+    ) -> bool {
+        match ko_rule {
+            KoRule::Simple => {
+                self.does_move_violate_simple_ko(player, player_move)
+            }
+            KoRule::PositionalSuperko => {
+                self.does_move_violate_positional_superko(player, player_move, past_situations)
+            }
+            KoRule::SituationalSuperko => {
+                self.does_move_violate_situational_superko(player, player_move, past_situations)
+            }
+        }
+    }
 
-        past_state = self.previous_state
-        while past_state is not None:
-            if past_state.situation == next_situation:
-                return True
-            past_state = past_state.previous_state
+    /// Метод определяет, нарушит ли ход игрока правило простого ко: ход не должен
+    /// восстанавливать позицию, которая была на доске перед последним ходом противника.
+    ///
+    /// # Arguments
+    ///
+    /// * `player`: Игрок делающий ход.
+    /// * `player_move`: Ход игрока.
+    ///
+    /// Returns: bool
+    fn does_move_violate_simple_ko(&self, player: Color, player_move: Move) -> bool {
+        match (self.next_board_hash(player, player_move), &self.previous_state) {
+            (Some(next_hash), Some(previous_state)) => {
+                previous_state.board.zobrist_hash() == next_hash
+            }
+            _ => { false }
+        }
+    }
 
-        return False
-        */
-        todo!()
+    /// Метод определяет, нарушит ли ход игрока правило ситуационного суперко: ход не
+    /// должен повторять ситуацию (расположение камней и игрока, который должен сделать ход),
+    /// уже встречавшуюся в игре.
+    ///
+    /// # Arguments
+    ///
+    /// * `player`: Игрок делающий ход.
+    /// * `player_move`: Ход игрока.
+    /// * `past_situations`: Ситуации, встречавшиеся в игре.
+    ///
+    /// Returns: bool
+    fn does_move_violate_situational_superko(
+        &self,
+        player: Color,
+        player_move: Move,
+        past_situations: &HashSet<(Color, u64)>
+    ) -> bool {
+        match self.next_board_hash(player, player_move) {
+            // Ситуация после хода: ход переходит к противнику.
+            Some(next_hash) => { past_situations.contains(&(player.other(), next_hash)) }
+            None => { false }
+        }
     }
 
-    ///  Показывает, является ли ход допустимым для данного игрового состояния.
+    /// Метод определяет, нарушит ли ход игрока правило позиционного суперко: ход не должен
+    /// повторять расположение камней, уже встречавшееся в игре (вне зависимости от того,
+    /// чья была очередь хода).
     ///
     /// # Arguments
     ///
+    /// * `player`: Игрок делающий ход.
     /// * `player_move`: Ход игрока.
+    /// * `past_situations`: Ситуации, встречавшиеся в игре.
     ///
     /// Returns: bool
-    fn is_valid_move(&self, player_move: Move) -> bool {
-
-
-        todo!()
-        /*
-        if self.is_over():
-            return False
-        if move.is_pass or move.is_resign:
-            return True
-        return (
-            self.board.get(move.point) is None
-            and not self.is_move_self_capture(self.next_player, move)
-            and self.does_move_violate_ko(self.next_player, move)
-        )
-        */
-    }
-}
\ No newline at end of file
+    fn does_move_violate_positional_superko(
+        &self,
+        player: Color,
+        player_move: Move,
+        past_situations: &HashSet<(Color, u64)>
+    ) -> bool {
+        match self.next_board_hash(player, player_move) {
+            Some(next_hash) => {
+                past_situations.contains(&(Color::Black, next_hash))
+                    || past_situations.contains(&(Color::White, next_hash))
+            }
+            None => { false }
+        }
+    }
+
+    /// Проверяет, является ли ход допустимым для данного игрового состояния.
+    ///
+    /// # Arguments
+    ///
+    /// * `player_move`: Ход игрока.
+    /// * `rules`: Правила игры.
+    /// * `past_situations`: Ситуации, встречавшиеся в игре.
+    ///
+    /// Returns: Result<(), IllegalMove> - причина, по которой ход недопустим
+    fn check_move(
+        &self,
+        player_move: Move,
+        rules: &RuleSet,
+        past_situations: &HashSet<(Color, u64)>
+    ) -> Result<(), IllegalMove> {
+        if self.is_over(rules) {
+            return Err(IllegalMove::GameOver);
+        }
+
+        // Ход должен делать игрок, чья очередь ходить. Выйти из игры можно в любой момент.
+        let is_resign = matches!(player_move, Move::Resign(_));
+        if !is_resign && player_move.get_color() != self.player_color {
+            return Err(IllegalMove::WrongColor {
+                expected: self.player_color,
+                played: player_move.get_color(),
+            });
+        }
+
+        match player_move {
+            Move::Pass(_) | Move::Resign(_) => { Ok(()) }
+            Move::Play((color, point)) => {
+                if !self.board.is_on_grid(&point) {
+                    Err(IllegalMove::OffBoard(point))
+                } else if self.board.get_color(&point).is_some() {
+                    Err(IllegalMove::Occupied(point))
+                } else if !rules.suicide_allowed && self.is_move_self_capture(color, player_move) {
+                    Err(IllegalMove::SelfCapture((color, point)))
+                } else if self.does_move_violate_ko(color, player_move, rules.ko, past_situations) {
+                    Err(IllegalMove::Ko((color, point)))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlgo::board::fixtures;
+
+    /// Делает серию ходов, начиная с черных.
+    fn play(game: &mut Game, points: &[(usize, usize)]) {
+        for &(row, col) in points {
+            let color = game.state.player_color;
+            game.apply_move(Move::Play((color, Point::new(row, col)))).unwrap();
+        }
+    }
+
+    /// Ход в занятую точку и ход не в свою очередь отклоняются.
+    #[test]
+    fn reject_occupied_point_and_wrong_color() {
+        let mut game = Game::new(9, RuleSet::chinese());
+        play(&mut game, &[(5, 5)]);
+
+        assert_eq!(
+            game.apply_move(Move::Play((Color::White, Point::new(5, 5)))),
+            Err(IllegalMove::Occupied(Point::new(5, 5)))
+        );
+        assert_eq!(
+            game.apply_move(Move::Play((Color::Black, Point::new(4, 4)))),
+            Err(IllegalMove::WrongColor { expected: Color::White, played: Color::Black })
+        );
+        assert!(game.apply_move(Move::Pass(Color::Black)).is_err());
+        assert_eq!(
+            game.apply_move(Move::Play((Color::White, Point::new(10, 5)))),
+            Err(IllegalMove::OffBoard(Point::new(10, 5)))
+        );
+
+        // Состояние игры не изменилось.
+        assert_eq!(game.state.player_color, Color::White);
+        assert!(game.apply_move(Move::Play((Color::White, Point::new(4, 4)))).is_ok());
+    }
+
+    /// Самозахват запрещен.
+    #[test]
+    fn reject_self_capture() {
+        let mut game = Game::new(9, RuleSet::chinese());
+        play(&mut game, &[(1, 2), (5, 5), (2, 1)]);
+
+        assert_eq!(
+            game.apply_move(Move::Play((Color::White, Point::new(1, 1)))),
+            Err(IllegalMove::SelfCapture((Color::White, Point::new(1, 1))))
+        );
+        assert_eq!(game.state.player_color, Color::White);
+    }
+
+    /// Ход, захватывающий камни, не является самозахватом.
+    #[test]
+    fn capture_is_not_self_capture() {
+        let mut game = Game::new(9, RuleSet::chinese());
+        play(&mut game, &[(1, 2), (1, 1), (5, 5)]);
+
+        // Белый камень в углу (1, 1) захватывается ходом черных в точку (2, 1).
+        play(&mut game, &[(6, 6), (2, 1)]);
+        assert!(game.state.board.get_go_string(&Point::new(1, 1)).is_none());
+
+        // Теперь белые не могут сыграть в угол: это самозахват.
+        assert!(game.apply_move(Move::Play((Color::White, Point::new(1, 1)))).is_err());
+    }
+
+    /// Немедленное повторное взятие ко запрещено.
+    #[test]
+    fn reject_ko_recapture() {
+        let mut game = Game::new(9, RuleSet::chinese());
+        play(&mut game, &[
+            (1, 2), (1, 3),
+            (2, 1), (2, 2),
+            (3, 2), (3, 3),
+            (9, 9), (2, 4),
+            (2, 3),
+        ]);
+
+        // Черные взяли камень (2, 2).
+        assert!(game.state.board.get_go_string(&Point::new(2, 2)).is_none());
+
+        // Белые не могут сразу взять обратно.
+        assert_eq!(
+            game.apply_move(Move::Play((Color::White, Point::new(2, 2)))),
+            Err(IllegalMove::Ko((Color::White, Point::new(2, 2))))
+        );
+
+        // Но после размена ходами в другом месте взятие допустимо.
+        play(&mut game, &[(8, 8), (7, 7), (2, 2)]);
+        assert!(game.state.board.get_go_string(&Point::new(2, 3)).is_none());
+    }
+
+    /// Ходы после завершения игры отклоняются.
+    #[test]
+    fn reject_move_after_game_over() {
+        let mut game = Game::new(9, RuleSet::chinese());
+        game.apply_move(Move::Pass(Color::Black)).unwrap();
+        game.apply_move(Move::Pass(Color::White)).unwrap();
+
+        assert!(game.is_over());
+        assert_eq!(
+            game.apply_move(Move::Play((Color::Black, Point::new(5, 5)))),
+            Err(IllegalMove::GameOver)
+        );
+    }
+
+    /// Генератор ходов: на пустой доске допустимы все точки, пропуск хода и выход из игры;
+    /// самозахват и взятие ко исключаются.
+    #[test]
+    fn legal_moves_honour_rules() {
+        let mut game = Game::new(9, RuleSet::chinese());
+        assert_eq!(game.legal_moves().len(), 81 + 2);
+
+        play(&mut game, &[
+            (1, 2), (1, 3),
+            (2, 1), (2, 2),
+            (3, 2), (3, 3),
+            (9, 9), (2, 4),
+            (2, 3),
+        ]);
+
+        let moves = game.legal_moves();
+        let is_listed = |row: usize, col: usize| moves.iter().any(|m| {
+            matches!(m, Move::Play((_, p)) if *p == Point::new(row, col))
+        });
+
+        // Взятие ко (2, 2) и самозахват в угол (1, 1) недопустимы.
+        assert!(!is_listed(2, 2));
+        assert!(!is_listed(1, 1));
+        assert!(!is_listed(2, 3));
+        assert!(is_listed(5, 5));
+
+        // Все ходы делаются белыми, последние два - пропуск хода и выход из игры.
+        assert!(moves.iter().all(|m| m.get_color() == Color::White));
+        assert!(matches!(moves[moves.len() - 2], Move::Pass(Color::White)));
+        assert!(matches!(moves[moves.len() - 1], Move::Resign(Color::White)));
+
+        // Каждый из перечисленных ходов действительно принимается игрой.
+        for candidate in moves {
+            let mut probe = Game::new(9, RuleSet::chinese());
+            probe.state = game.state.clone();
+            probe.situations = game.situations.clone();
+            assert!(probe.apply_move(candidate).is_ok());
+        }
+    }
+
+    /// Самозахват цепочки допустим только по правилам, разрешающим самозахват.
+    #[test]
+    fn suicide_depends_on_rules() {
+        let moves = [(1, 3), (1, 1), (2, 1), (9, 9), (2, 2)];
+
+        let mut chinese = Game::new(9, RuleSet::chinese());
+        play(&mut chinese, &moves);
+        assert!(chinese.apply_move(Move::Play((Color::White, Point::new(1, 2)))).is_err());
+
+        let mut tromp_taylor = Game::new(9, RuleSet::tromp_taylor());
+        play(&mut tromp_taylor, &moves);
+        assert!(tromp_taylor.apply_move(Move::Play((Color::White, Point::new(1, 2)))).is_ok());
+
+        // Цепочка из двух белых камней снята с доски.
+        assert!(tromp_taylor.state.board.get_color(&Point::new(1, 1)).is_none());
+        assert!(tromp_taylor.state.board.get_color(&Point::new(1, 2)).is_none());
+    }
+
+    /// Простое ко запрещает немедленное взятие обратно.
+    #[test]
+    fn simple_ko_forbids_immediate_recapture() {
+        let mut game = Game::new(9, RuleSet::japanese());
+        play(&mut game, &[
+            (1, 2), (1, 3),
+            (2, 1), (2, 2),
+            (3, 2), (3, 3),
+            (9, 9), (2, 4),
+            (2, 3),
+        ]);
+
+        assert!(game.apply_move(Move::Play((Color::White, Point::new(2, 2)))).is_err());
+
+        // После размена ходами в другом месте стороны поочередно берут ко.
+        play(&mut game, &[(8, 8), (7, 7), (2, 2)]);
+        assert!(game.state.board.get_color(&Point::new(2, 3)).is_none());
+
+        assert!(game.apply_move(Move::Play((Color::Black, Point::new(2, 3)))).is_err());
+        play(&mut game, &[(6, 6), (5, 5), (2, 3)]);
+        assert!(game.state.board.get_color(&Point::new(2, 2)).is_none());
+    }
+
+    /// По правилу "камня за пас" последний пас должны делать белые.
+    #[test]
+    fn pass_stones_require_white_to_pass_last() {
+        let mut game = Game::new(9, RuleSet::aga());
+        game.apply_move(Move::Play((Color::Black, Point::new(5, 5)))).unwrap();
+        game.apply_move(Move::Pass(Color::White)).unwrap();
+        game.apply_move(Move::Pass(Color::Black)).unwrap();
+        assert!(!game.is_over());
+
+        game.apply_move(Move::Pass(Color::White)).unwrap();
+        assert!(game.is_over());
+
+        let mut game = Game::new(9, RuleSet::chinese());
+        game.apply_move(Move::Play((Color::Black, Point::new(5, 5)))).unwrap();
+        game.apply_move(Move::Pass(Color::White)).unwrap();
+        game.apply_move(Move::Pass(Color::Black)).unwrap();
+        assert!(game.is_over());
+    }
+
+    /// Результат партии: выход из игры (в том числе не в свою очередь) и подсчет очков по
+    /// площади с учетом коми.
+    #[test]
+    fn result_after_resignation_and_passes() {
+        let mut game = Game::new(9, RuleSet::chinese());
+        assert!(game.result().is_none());
+
+        play(&mut game, &[(5, 5)]);
+        game.apply_move(Move::Resign(Color::White)).unwrap();
+        assert_eq!(game.result(), Some(GameResult::Resignation(Color::Black)));
+
+        // Игрок может выйти из игры и не в свою очередь.
+        let mut game = Game::new(9, RuleSet::chinese());
+        play(&mut game, &[(5, 5)]);
+        game.apply_move(Move::Resign(Color::Black)).unwrap();
+        assert_eq!(game.result(), Some(GameResult::Resignation(Color::White)));
+
+        // Черная стена по 5 колонке: черным принадлежат колонки 1-5 (45 очков), белым
+        // колонки 6-9 (36 очков) и коми 7.5.
+        let mut game = Game::new(9, RuleSet::chinese());
+        fixtures::play_diagram(&mut game, fixtures::WALLS);
+        game.apply_move(Move::Pass(Color::Black)).unwrap();
+        game.apply_move(Move::Pass(Color::White)).unwrap();
+        assert_eq!(game.result(), Some(GameResult::Score(Color::Black, 1.5)));
+
+        // С коми 9.5 побеждают белые, с целым коми 9 - ничья.
+        game.set_komi(9.5);
+        assert_eq!(game.result().unwrap().to_string(), "W+0.5");
+        game.set_komi(9.0);
+        assert_eq!(game.result(), Some(GameResult::Draw));
+    }
+
+    /// Снятые камни и камни, отданные за пас, учитываются как пленные.
+    #[test]
+    fn prisoners_from_captures_and_passes() {
+        let mut game = Game::new(9, RuleSet::aga());
+        play(&mut game, &[(1, 2), (1, 1), (2, 1)]);
+        assert_eq!(game.prisoners(Color::Black), 1);
+        assert_eq!(game.prisoners(Color::White), 0);
+
+        // По правилам AGA пропускающий ход игрок отдает камень противнику.
+        game.apply_move(Move::Pass(Color::White)).unwrap();
+        assert_eq!(game.prisoners(Color::Black), 2);
+    }
+
+    /// Мертвые камни отмечаются после двух пасов и учитываются при подсчете очков.
+    #[test]
+    fn dead_stones_change_the_score() {
+        // Черная стена по 5 колонке, белая по 6 и белый камень (2, 2) в черной области.
+        let mut game = Game::new(9, RuleSet::japanese());
+        fixtures::play_diagram(&mut game, fixtures::WALLS);
+        game.apply_move(Move::Pass(Color::Black)).unwrap();
+        play(&mut game, &[(2, 2)]);
+        assert!(game.toggle_dead_string(&Point::new(2, 2)).is_err());
+
+        game.apply_move(Move::Pass(Color::Black)).unwrap();
+        game.apply_move(Move::Pass(Color::White)).unwrap();
+        assert!(game.is_marking());
+        assert!(game.toggle_dead_string(&Point::new(3, 3)).is_err());
+
+        // Без отметки область слева граничит с камнями обоих цветов и никому не
+        // принадлежит: у белых 27 очков территории и коми 6.5.
+        assert_eq!(game.result(), Some(GameResult::Score(Color::White, 33.5)));
+
+        // Мертвый камень становится пленным черных, а его точка - их территорией.
+        game.toggle_dead_string(&Point::new(2, 2)).unwrap();
+        assert!(game.is_dead(&Point::new(2, 2)));
+        assert!(matches!(game.get_display_point(2, 2), DisplayPoint::DeadWhiteStone));
+        assert_eq!(game.score(), (37.0, 27.0));
+        assert_eq!(game.result(), Some(GameResult::Score(Color::Black, 3.5)));
+
+        // Повторная отметка снимает ее.
+        game.toggle_dead_string(&Point::new(2, 2)).unwrap();
+        assert!(game.dead_stones().is_empty());
+
+        // Оценка доигровками находит тот же мертвый камень.
+        game.estimate_dead_stones(20).unwrap();
+        assert!(game.is_dead(&Point::new(2, 2)));
+        assert_eq!(game.dead_stones().len(), 1);
+    }
+
+    /// При подсчете по площади мертвые камни также снимаются с доски.
+    #[test]
+    fn dead_stones_in_area_scoring() {
+        let mut game = Game::new(9, RuleSet::chinese());
+        fixtures::play_diagram(&mut game, fixtures::WALLS);
+        game.apply_move(Move::Pass(Color::Black)).unwrap();
+        play(&mut game, &[(2, 2)]);
+        game.apply_move(Move::Pass(Color::Black)).unwrap();
+        game.apply_move(Move::Pass(Color::White)).unwrap();
+
+        // Без отметки у черных только 9 камней стены против 37 очков и коми 7.5 у белых.
+        assert_eq!(game.result().unwrap().to_string(), "W+35.5");
+
+        game.toggle_dead_string(&Point::new(2, 2)).unwrap();
+        assert_eq!(game.territory().black_area(), 45);
+        assert_eq!(game.result().unwrap().to_string(), "B+1.5");
+    }
+
+    /// Глаза цепочек в секи не засчитываются при подсчете по территории, но засчитываются
+    /// при подсчете по площади.
+    #[test]
+    fn seki_eyes_in_territory_scoring() {
+        let mut japanese = Game::new(5, RuleSet::japanese());
+        fixtures::play_diagram(&mut japanese, fixtures::SEKI_WITH_EYES);
+
+        // Черным остается только пятый ряд, у белых территории нет.
+        assert_eq!(japanese.score(), (5.0, 0.0));
+
+        let mut chinese = Game::new(5, RuleSet::chinese());
+        fixtures::play_diagram(&mut chinese, fixtures::SEKI_WITH_EYES);
+        assert_eq!(chinese.score(), (15.0, 9.0));
+    }
+
+    /// В игре с форой камни стоят на доске до первого хода, а первыми ходят белые.
+    #[test]
+    fn handicap_stones_and_white_to_move() {
+        let mut game = Game::with_fixed_handicap(9, RuleSet::japanese(), 2).unwrap();
+        assert_eq!(game.handicap(), [Point::new(7, 3), Point::new(3, 7)]);
+        assert!(matches!(game.get_display_point(7, 3), DisplayPoint::BlackStone));
+        assert_eq!(game.komi(), RuleSet::japanese().handicap_komi(2));
+
+        assert!(game.apply_move(Move::Play((Color::Black, Point::new(5, 5)))).is_err());
+        game.apply_move(Move::Play((Color::White, Point::new(5, 5)))).unwrap();
+
+        // Камни форы стоят в начальной позиции и не являются ходами партии.
+        assert_eq!(game.moves().collect::<Vec<_>>(), [Move::Play((Color::White, Point::new(5, 5)))]);
+        let initial = game.history().next().unwrap();
+        assert_eq!(initial.board().get_color(&Point::new(7, 3)), Some(Color::Black));
+
+        let points = [Point::new(1, 1), Point::new(2, 2), Point::new(3, 3)];
+        let game = Game::with_free_handicap(9, RuleSet::chinese(), &points).unwrap();
+        assert_eq!(game.handicap(), points);
+        assert_eq!(game.state.player_color, Color::White);
+
+        assert!(Game::with_free_handicap(9, RuleSet::chinese(), &points[..1]).is_err());
+        assert!(Game::with_fixed_handicap(9, RuleSet::chinese(), 10).is_err());
+    }
+
+    /// Отмена и возврат ходов восстанавливают позиции, а новый ход очищает стек возврата.
+    #[test]
+    fn undo_redo_and_goto_move() {
+        let mut game = Game::new(9, RuleSet::chinese());
+        assert!(!game.undo());
+        play(&mut game, &[(1, 2), (1, 1), (2, 1)]);
+        assert_eq!(game.move_number(), 3);
+        assert_eq!(game.prisoners(Color::Black), 1);
+
+        assert!(game.undo());
+        assert_eq!(game.state().board().get_color(&Point::new(1, 1)), Some(Color::White));
+        assert_eq!(game.state().player_color(), Color::Black);
+        assert_eq!(game.prisoners(Color::Black), 0);
+
+        assert!(game.redo());
+        assert!(!game.redo());
+        assert_eq!(game.state().board().get_color(&Point::new(1, 1)), None);
+
+        game.goto_move(0).unwrap();
+        assert_eq!(game.moves().count(), 0);
+        game.goto_move(2).unwrap();
+        assert_eq!(game.move_number(), 2);
+        assert!(game.goto_move(4).is_err());
+
+        // Новый ход из середины партии отбрасывает отмененную ветвь.
+        play(&mut game, &[(5, 5)]);
+        assert!(!game.redo());
+
+        let moves: Vec<String> = game.moves().map(|mv| mv.to_string()).collect();
+        assert_eq!(moves.len(), 3);
+        assert_eq!(game.history().count(), 4);
+        assert_eq!(game.history().last().unwrap().move_number(), 3);
+    }
+
+    /// После отмены завершающего паса игру можно продолжить, а ко проверяется по
+    /// восстановленной истории.
+    #[test]
+    fn undo_resumes_finished_game() {
+        let mut game = Game::new(9, RuleSet::chinese());
+        play(&mut game, &[(5, 5)]);
+        game.apply_move(Move::Pass(Color::White)).unwrap();
+        game.apply_move(Move::Pass(Color::Black)).unwrap();
+        assert!(game.is_over());
+
+        assert!(game.undo());
+        assert!(!game.is_over());
+        game.apply_move(Move::Play((Color::Black, Point::new(4, 4)))).unwrap();
+    }
+
+    /// Каждое состояние хранит камни, снятые ходом, а пленные накапливаются по ходам.
+    #[test]
+    fn capture_history_per_move() {
+        let mut game = Game::new(9, RuleSet::chinese());
+        play(&mut game, &[(1, 2), (1, 1), (2, 1), (5, 5)]);
+
+        let captures: Vec<&[Point]> = game.history().map(|state| state.captured()).collect();
+        let expected: [&[Point]; 5] = [&[], &[], &[], &[Point::new(1, 1)], &[]];
+        assert_eq!(captures, expected);
+        assert!(game.last_captures().is_empty());
+        assert_eq!(game.state().prisoners(Color::Black), 1);
+
+        game.undo();
+        assert_eq!(game.last_captures(), [Point::new(1, 1)]);
+    }
+
+    /// Игра на прямоугольной доске: ходы и отображение используют обе размерности.
+    #[test]
+    fn rectangular_board() {
+        let mut game = Game::new_rectangular(5, 13, RuleSet::chinese()).unwrap();
+        assert_eq!((game.num_rows(), game.num_cols()), (5, 13));
+        assert_eq!(game.legal_moves().len(), 5 * 13 + 2);
+
+        game.apply_move(Move::Play((Color::Black, Point::new(5, 13)))).unwrap();
+        assert!(game.apply_move(Move::Play((Color::White, Point::new(13, 5)))).is_err());
+
+        let display = game.get_display_state();
+        assert_eq!(display.len(), 5);
+        assert!(display.iter().all(|row| row.len() == 13));
+        assert!(matches!(display[4][12], DisplayPoint::BlackStone));
+
+        assert!(Game::new_rectangular(25, 25, RuleSet::chinese()).is_ok());
+        assert_eq!(
+            Game::new_rectangular(1, 9, RuleSet::chinese()).err().unwrap().to_string(),
+            "Размер доски 1x9: число строк и колонок должно быть от 2 до 25"
+        );
+        assert!(Game::new_rectangular(9, 26, RuleSet::chinese()).is_err());
+    }
+
+    /// Основные типы можно передавать между потоками и разделять между ними.
+    #[test]
+    fn core_types_are_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Board>();
+        assert_send_sync::<Game>();
+        assert_send_sync::<GameState>();
+        assert_send_sync::<crate::dlgo::board::game_tree::GameTree>();
+
+        // Позиция, разделяемая с другим потоком (например, фоновым анализом).
+        let mut game = Game::new(9, RuleSet::chinese());
+        play(&mut game, &[(5, 5), (3, 3)]);
+        let state = game.shared_state();
+
+        let hash = std::thread::spawn(move || state.board().zobrist_hash()).join().unwrap();
+        assert_eq!(hash, game.state().board().zobrist_hash());
+    }
+}
@@ -0,0 +1,337 @@
+//! Дерево партии с вариантами.
+//!
+//! Каждый узел дерева хранит состояние игры (GameState) после хода. Состояния неизменяемы и
+//! разделяются через Arc: узел ссылается на то же состояние, что и игра, а доски не
+//! копируются. У узла может быть несколько дочерних ходов; первый из них продолжает
+//! основную линию, остальные являются вариантами.
+
+#![allow(dead_code)]
+
+use std::sync::Arc;
+use crate::dlgo::error::FatalError;
+use crate::dlgo::board::game::{Game, GameState};
+use crate::dlgo::gotypes::Move;
+
+/// Идентификатор узла дерева партии.
+pub type NodeId = usize;
+
+/// Узел дерева партии.
+struct Node {
+    state: Arc<GameState>,
+    parent: Option<NodeId>,
+    // Дочерние узлы: первый - продолжение основной линии, остальные - варианты.
+    children: Vec<NodeId>,
+    comment: String,
+}
+
+/// Дерево партии: игра и все исследованные в ней варианты.
+pub struct GameTree {
+    // Игра, текущее состояние которой соответствует текущему узлу.
+    game: Game,
+    // Узлы дерева (корень - нулевой узел).
+    nodes: Vec<Node>,
+    current: NodeId,
+}
+
+impl GameTree {
+    /// Идентификатор корневого узла.
+    pub const ROOT: NodeId = 0;
+
+    /// Конструктор. Корнем дерева становится текущее состояние переданной игры.
+    ///
+    /// # Arguments
+    ///
+    /// * `game`: Игра.
+    ///
+    /// Returns: GameTree
+    pub fn new(game: Game) -> Self {
+        let root = Node {
+            state: game.shared_state(),
+            parent: None,
+            children: Vec::new(),
+            comment: String::new(),
+        };
+
+        GameTree {
+            game,
+            nodes: vec![root],
+            current: Self::ROOT,
+        }
+    }
+
+    /// Делает ход из текущего узла. Если такой ход уже есть среди дочерних узлов, дерево
+    /// переходит в существующий узел, иначе создается новый вариант.
+    ///
+    /// # Arguments
+    ///
+    /// * `player_move`: Ход игрока.
+    ///
+    /// Returns: Result<NodeId, FatalError> - узел, ставший текущим
+    pub fn play(&mut self, player_move: Move) -> Result<NodeId, FatalError> {
+        let existing = self.nodes[self.current].children.iter()
+            .find(|&&child| self.nodes[child].state.last_move() == Some(player_move))
+            .copied();
+
+        if let Some(child) = existing {
+            self.goto(child)?;
+            return Ok(child);
+        }
+
+        self.game.apply_move(player_move)?;
+
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            state: self.game.shared_state(),
+            parent: Some(self.current),
+            children: Vec::new(),
+            comment: String::new(),
+        });
+        self.nodes[self.current].children.push(id);
+        self.current = id;
+
+        Ok(id)
+    }
+
+    /// Переходит в произвольный узел дерева.
+    ///
+    /// # Arguments
+    ///
+    /// * `node`: Узел дерева.
+    ///
+    /// Returns: Result<(), FatalError>
+    pub fn goto(&mut self, node: NodeId) -> Result<(), FatalError> {
+        self.check(node)?;
+
+        self.current = node;
+        self.game.jump_to(self.nodes[node].state.clone());
+
+        Ok(())
+    }
+
+    /// Переходит к родительскому узлу.
+    ///
+    /// Returns: bool - false, если текущий узел корневой
+    pub fn back(&mut self) -> bool {
+        match self.nodes[self.current].parent {
+            Some(parent) => { self.goto(parent).is_ok() }
+            None => { false }
+        }
+    }
+
+    /// Переходит к следующему ходу основной линии текущего узла.
+    ///
+    /// Returns: bool - false, если у текущего узла нет дочерних ходов
+    pub fn forward(&mut self) -> bool {
+        self.select_variation(0)
+    }
+
+    /// Переходит к дочернему ходу с переданным номером (0 - основная линия).
+    ///
+    /// # Arguments
+    ///
+    /// * `index`: Номер варианта среди дочерних ходов текущего узла.
+    ///
+    /// Returns: bool - false, если варианта с таким номером нет
+    pub fn select_variation(&mut self, index: usize) -> bool {
+        match self.nodes[self.current].children.get(index) {
+            Some(&child) => { self.goto(child).is_ok() }
+            None => { false }
+        }
+    }
+
+    /// Делает вариант основной линией: узел и все его предки становятся первыми среди
+    /// дочерних узлов своих родителей.
+    ///
+    /// # Arguments
+    ///
+    /// * `node`: Узел варианта.
+    ///
+    /// Returns: Result<(), FatalError>
+    pub fn promote_variation(&mut self, node: NodeId) -> Result<(), FatalError> {
+        self.check(node)?;
+
+        let mut child = node;
+        while let Some(parent) = self.nodes[child].parent {
+            let siblings = &mut self.nodes[parent].children;
+            if let Some(position) = siblings.iter().position(|&id| id == child) {
+                siblings[..=position].rotate_right(1);
+            }
+
+            child = parent;
+        }
+
+        Ok(())
+    }
+
+    /// Узлы основной линии от корня до конца.
+    pub fn main_line(&self) -> Vec<NodeId> {
+        let mut line = vec![Self::ROOT];
+
+        while let Some(&child) = self.nodes[*line.last().unwrap()].children.first() {
+            line.push(child);
+        }
+
+        line
+    }
+
+    /// Показывает, лежит ли узел на основной линии (false для несуществующего узла).
+    pub fn is_main_line(&self, node: NodeId) -> bool {
+        if self.check(node).is_err() {
+            return false;
+        }
+
+        let mut child = node;
+        while let Some(parent) = self.nodes[child].parent {
+            if self.nodes[parent].children.first() != Some(&child) {
+                return false;
+            }
+
+            child = parent;
+        }
+
+        true
+    }
+
+    /// Устанавливает комментарий к узлу.
+    ///
+    /// # Arguments
+    ///
+    /// * `node`: Узел дерева.
+    /// * `comment`: Текст комментария.
+    ///
+    /// Returns: Result<(), FatalError>
+    pub fn set_comment(&mut self, node: NodeId, comment: &str) -> Result<(), FatalError> {
+        self.check(node)?;
+        self.nodes[node].comment = comment.to_string();
+
+        Ok(())
+    }
+
+    /// Комментарий к узлу (пустая строка, если комментария нет).
+    ///
+    /// # Arguments
+    ///
+    /// * `node`: Узел дерева.
+    ///
+    /// Returns: Result<&str, FatalError>
+    pub fn comment(&self, node: NodeId) -> Result<&str, FatalError> {
+        self.check(node)?;
+
+        Ok(&self.nodes[node].comment)
+    }
+
+    /// Текущий узел.
+    pub fn current(&self) -> NodeId {
+        self.current
+    }
+
+    /// Родительский узел.
+    ///
+    /// # Arguments
+    ///
+    /// * `node`: Узел дерева.
+    ///
+    /// Returns: Result<Option<NodeId>, FatalError> - None для корня
+    pub fn parent(&self, node: NodeId) -> Result<Option<NodeId>, FatalError> {
+        self.check(node)?;
+
+        Ok(self.nodes[node].parent)
+    }
+
+    /// Дочерние узлы: первый - продолжение основной линии, остальные - варианты.
+    ///
+    /// # Arguments
+    ///
+    /// * `node`: Узел дерева.
+    ///
+    /// Returns: Result<&[NodeId], FatalError>
+    pub fn children(&self, node: NodeId) -> Result<&[NodeId], FatalError> {
+        self.check(node)?;
+
+        Ok(&self.nodes[node].children)
+    }
+
+    /// Состояние игры в узле.
+    ///
+    /// # Arguments
+    ///
+    /// * `node`: Узел дерева.
+    ///
+    /// Returns: Result<&GameState, FatalError>
+    pub fn state(&self, node: NodeId) -> Result<&GameState, FatalError> {
+        self.check(node)?;
+
+        Ok(&self.nodes[node].state)
+    }
+
+    /// Игра в текущем узле.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Проверяет, что узел существует в дереве.
+    fn check(&self, node: NodeId) -> Result<(), FatalError> {
+        if node >= self.nodes.len() {
+            let err = FatalError::new(format!("В дереве партии нет узла {}", node));
+
+            return Err(err);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlgo::board::rules::RuleSet;
+    use crate::dlgo::gotypes::{Color, Point};
+
+    fn black(row: usize, col: usize) -> Move {
+        Move::Play((Color::Black, Point::new(row, col)))
+    }
+
+    fn white(row: usize, col: usize) -> Move {
+        Move::Play((Color::White, Point::new(row, col)))
+    }
+
+    /// Варианты ответвляются от узла, разделяют с игрой состояния и могут стать
+    /// основной линией.
+    #[test]
+    fn variations_and_promotion() {
+        let mut tree = GameTree::new(Game::new(9, RuleSet::chinese()));
+        let first = tree.play(black(3, 3)).unwrap();
+        let main = tree.play(white(7, 7)).unwrap();
+
+        tree.back();
+        let variation = tree.play(white(3, 4)).unwrap();
+        assert_eq!(tree.children(first).unwrap(), [main, variation]);
+        assert!(!tree.is_main_line(variation));
+        assert!(Arc::ptr_eq(&tree.nodes[variation].state, &tree.game().shared_state()));
+
+        // Повторный ход переходит в существующий узел.
+        tree.back();
+        assert_eq!(tree.play(white(7, 7)).unwrap(), main);
+        assert_eq!(tree.nodes.len(), 4);
+
+        // Переход между ветвями меняет позицию игры.
+        tree.goto(variation).unwrap();
+        let board = tree.game().state().board();
+        assert_eq!(board.get_color(&Point::new(3, 4)), Some(Color::White));
+        assert_eq!(board.get_color(&Point::new(7, 7)), None);
+        tree.back();
+        assert!(tree.select_variation(1));
+        assert_eq!(tree.current(), variation);
+
+        tree.promote_variation(variation).unwrap();
+        assert_eq!(tree.main_line(), [GameTree::ROOT, first, variation]);
+        assert!(tree.is_main_line(variation));
+
+        tree.set_comment(variation, "Лучше").unwrap();
+        assert_eq!(tree.comment(variation).unwrap(), "Лучше");
+        assert!(tree.goto(42).is_err());
+        assert!(tree.comment(42).is_err() && tree.children(42).is_err());
+        assert!(tree.parent(42).is_err() && tree.state(42).is_err());
+        assert!(!tree.is_main_line(42));
+    }
+}
@@ -0,0 +1,185 @@
+//! Таблицы геометрии доски.
+//!
+//! Для каждого размера доски один раз вычисляются соседние и диагональные точки в пределах
+//! доски, расстояние до края и класс точки (угол, край, центр). Таблицы разделяются всеми
+//! досками одного размера (через Arc, в том числе между потоками), поэтому вызывающему
+//! коду не нужно проверять, лежат ли соседние точки на доске.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use crate::dlgo::gotypes::Point;
+
+/// Положение точки на доске.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub enum PointClass {
+    /// Угол доски: у точки две соседние точки.
+    Corner,
+    /// Край доски: у точки три соседние точки.
+    Edge,
+    /// Внутренняя точка доски.
+    Center,
+}
+
+/// Таблицы геометрии доски заданного размера.
+#[derive(Debug)]
+pub struct Geometry {
+    num_rows: usize,
+    num_cols: usize,
+    // Таблицы по точкам доски, индекс точки (row, col) равен (row - 1) * num_cols + (col - 1).
+    neighbors: Vec<Vec<Point>>,
+    diagonals: Vec<Vec<Point>>,
+    edge_distance: Vec<usize>,
+    class: Vec<PointClass>,
+}
+
+// Таблицы, уже построенные для размеров доски (число строк, число колонок).
+type Cache = Mutex<HashMap<(usize, usize), Arc<Geometry>>>;
+
+static GEOMETRIES: OnceLock<Cache> = OnceLock::new();
+
+impl Geometry {
+    /// Возвращает таблицы для доски заданного размера, строя их при первом обращении.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_rows`: Число строк доски.
+    /// * `num_cols`: Число колонок доски.
+    ///
+    /// Returns: Arc<Geometry>
+    pub fn for_size(num_rows: usize, num_cols: usize) -> Arc<Geometry> {
+        let geometries = GEOMETRIES.get_or_init(|| Mutex::new(HashMap::new()));
+
+        // Кэш только дополняется, поэтому после паники другого потока им можно пользоваться.
+        let mut geometries = geometries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        geometries.entry((num_rows, num_cols))
+            .or_insert_with(|| Arc::new(Geometry::new(num_rows, num_cols)))
+            .clone()
+    }
+
+    /// Строит таблицы для доски заданного размера.
+    fn new(num_rows: usize, num_cols: usize) -> Self {
+        let mut geometry = Geometry {
+            num_rows,
+            num_cols,
+            neighbors: Vec::with_capacity(num_rows * num_cols),
+            diagonals: Vec::with_capacity(num_rows * num_cols),
+            edge_distance: Vec::with_capacity(num_rows * num_cols),
+            class: Vec::with_capacity(num_rows * num_cols),
+        };
+
+        for row in 1..=num_rows {
+            for col in 1..=num_cols {
+                let (row, col) = (row as isize, col as isize);
+
+                let neighbors = [(0, -1), (0, 1), (-1, 0), (1, 0)].iter()
+                    .filter_map(|&(dr, dc)| geometry.offset(row + dr, col + dc))
+                    .collect::<Vec<Point>>();
+
+                let diagonals = [(-1, -1), (-1, 1), (1, -1), (1, 1)].iter()
+                    .filter_map(|&(dr, dc)| geometry.offset(row + dr, col + dc))
+                    .collect::<Vec<Point>>();
+
+                let (row, col) = (row as usize, col as usize);
+                let edge_distance = (row - 1).min(num_rows - row).min(col - 1).min(num_cols - col);
+
+                let class = match neighbors.len() {
+                    4 => { PointClass::Center }
+                    3 => { PointClass::Edge }
+                    // На досках шириной в одну-две линии у точек может быть меньше соседей.
+                    _ => { PointClass::Corner }
+                };
+
+                geometry.neighbors.push(neighbors);
+                geometry.diagonals.push(diagonals);
+                geometry.edge_distance.push(edge_distance);
+                geometry.class.push(class);
+            }
+        }
+
+        geometry
+    }
+
+    /// Точка со знаковыми координатами, если она лежит на доске.
+    fn offset(&self, row: isize, col: isize) -> Option<Point> {
+        let on_grid = 1 <= row && row <= self.num_rows as isize
+            && 1 <= col && col <= self.num_cols as isize;
+
+        if on_grid {
+            Some(Point::new(row as usize, col as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Индекс точки в таблицах.
+    fn index(&self, point: &Point) -> usize {
+        (point.row - 1) * self.num_cols + (point.col - 1)
+    }
+
+    /// Число строк доски.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Число колонок доски.
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// Соседние точки в пределах доски (от двух до четырех). Точка должна лежать на доске.
+    pub fn neighbors(&self, point: &Point) -> &[Point] {
+        &self.neighbors[self.index(point)]
+    }
+
+    /// Диагональные точки в пределах доски (от одной до четырех). Точка должна лежать на
+    /// доске.
+    pub fn diagonals(&self, point: &Point) -> &[Point] {
+        &self.diagonals[self.index(point)]
+    }
+
+    /// Расстояние до ближайшего края доски (0 для точек первой линии).
+    pub fn edge_distance(&self, point: &Point) -> usize {
+        self.edge_distance[self.index(point)]
+    }
+
+    /// Положение точки на доске: угол, край или центр.
+    pub fn class(&self, point: &Point) -> PointClass {
+        self.class[self.index(point)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Соседи, диагонали, расстояние до края и класс точек на прямоугольной доске.
+    #[test]
+    fn tables_for_rectangular_board() {
+        let geometry = Geometry::for_size(5, 7);
+
+        let corner = Point::new(1, 1);
+        assert_eq!(geometry.neighbors(&corner), [Point::new(1, 2), Point::new(2, 1)]);
+        assert_eq!(geometry.diagonals(&corner), [Point::new(2, 2)]);
+        assert_eq!(geometry.class(&corner), PointClass::Corner);
+
+        let edge = Point::new(5, 4);
+        assert_eq!(geometry.neighbors(&edge).len(), 3);
+        assert_eq!(geometry.class(&edge), PointClass::Edge);
+        assert_eq!(geometry.edge_distance(&edge), 0);
+
+        let center = Point::new(3, 4);
+        assert_eq!(geometry.neighbors(&center).len(), 4);
+        assert_eq!(geometry.diagonals(&center).len(), 4);
+        assert_eq!(geometry.edge_distance(&center), 2);
+        assert_eq!(geometry.class(&Point::new(3, 6)), PointClass::Center);
+
+        // Таблицы строятся один раз для каждого размера.
+        assert!(Arc::ptr_eq(&geometry, &Geometry::for_size(5, 7)));
+        assert!(!Arc::ptr_eq(&geometry, &Geometry::for_size(7, 5)));
+    }
+}
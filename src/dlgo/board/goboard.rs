@@ -3,28 +3,124 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
-use std::collections::{HashMap};
-use std::rc::Rc;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+use crate::dlgo::board::benson::{self, UnconditionalLife};
+use crate::dlgo::board::geometry::Geometry;
 use crate::dlgo::board::gostring::GoString;
-use crate::dlgo::error::FatalError;
-use crate::dlgo::gotypes::{Point, Stone};
+use crate::dlgo::error::{FatalError, IllegalMove};
+use crate::dlgo::gotypes::{Color, Point, Stone};
+use crate::dlgo::zobrist;
+
+/// Содержимое ячейки доски.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+enum Content {
+    Empty,
+    Stone(Color),
+    // Ячейка рамки вокруг игрового поля (за пределами сетки доски).
+    Border,
+}
+
+/// Ячейка доски. Поля `head` и `next` задают принадлежность камня цепочке: каждый камень
+/// ссылается на "голову" своей цепочки, а камни цепочки связаны в кольцевой список.
+/// Счетчики цепочки (размер и псевдо-свободы) хранятся только в ячейке головы цепочки.
+#[derive(Copy, Clone)]
+struct Cell {
+    content: Content,
+    // Индекс ячейки головы цепочки.
+    head: u16,
+    // Индекс следующего камня цепочки (кольцевой список).
+    next: u16,
+    // Число камней в цепочке.
+    size: u16,
+    // Псевдо-свободы: число пар (камень цепочки, соседняя пустая точка). Одна и та же
+    // пустая точка учитывается столько раз, скольких камней цепочки она касается.
+    libs: u16,
+    // Сумма индексов и сумма квадратов индексов псевдо-свобод. Позволяют за O(1)
+    // определить, что у цепочки осталась ровно одна степень свободы (атари).
+    lib_sum: u32,
+    lib_sum_sq: u32,
+}
+
+impl Cell {
+    fn new(content: Content) -> Self {
+        Cell {
+            content,
+            head: 0,
+            next: 0,
+            size: 0,
+            libs: 0,
+            lib_sum: 0,
+            lib_sum_sq: 0,
+        }
+    }
+}
+
+/// Запись стека отмены ходов.
+#[derive(Copy, Clone)]
+enum UndoEntry {
+    // Начало хода, сделанного методом Board::play, и хеш доски до хода.
+    Move { hash: u64 },
+    // Прежнее значение ячейки, измененной ходом.
+    Cell { idx: u16, cell: Cell },
+}
+
+/// Наименьший поддерживаемый размер стороны доски.
+pub const MIN_BOARD_SIZE: usize = 2;
+
+/// Наибольший поддерживаемый размер стороны доски (ограничен обозначениями колонок A-Z без I).
+pub const MAX_BOARD_SIZE: usize = 25;
+
+/// Обозначения колонок доски в диаграммах (как в протоколе GTP, без буквы I).
+const COLUMN_LETTERS: &str = "ABCDEFGHJKLMNOPQRSTUVWXYZ";
+
+/// Вид глаза: пустой точки, все соседи которой - камни одного цвета.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub enum EyeKind {
+    /// Настоящий глаз: игрок контролирует достаточно диагональных точек, и противник не
+    /// может разрушить глаз. Заполнять такой глаз своим камнем нельзя.
+    True,
+    /// Ложный глаз: противник контролирует диагональные точки и может, захватив часть
+    /// окружающих камней, сделать в этой точке ход.
+    False,
+}
 
 /// Структура, представляющая доску для игры в Go. Реализует типаж Clone (в связи
 /// с необходимостью хранить несколько конфигураций доски). Доска содержит цепочки
-/// камней.
+/// камней. Доски сравниваются на равенство (типаж PartialEq) по расположению камней.
+///
+/// Доска хранится в плоском массиве ячеек размера (num_rows + 2) x (num_cols + 2): вокруг
+/// игрового поля находится рамка из ячеек Content::Border, поэтому у любой точки поля есть
+/// ровно четыре соседние ячейки, и проверка границ при обходе соседей не нужна. Индекс
+/// ячейки точки (row, col) равен row * stride + col, т.к. строки и столбцы нумеруются с 1.
+/// Клонирование доски - это копирование одного вектора (таблицы геометрии разделяются).
 #[derive(Clone)]
 pub struct Board {
     num_rows: usize,
     num_cols: usize,
-    // Словарь, хранящий цепочки камней. Клюём в словаре являются точки на
-    // доске занятые камнями, а значениями цепочки камней. Цепочки камней
-    // обернуты в Rc (в тип с подсчетом ссылок), т.к. каждый камень (через
-    // отображение) должен ссылаться на свою-же цепочку.
-    grid: HashMap<Point, Rc<GoString>>
+    // Длина строки массива ячеек (num_cols + 2).
+    stride: usize,
+    cells: Vec<Cell>,
+    // Таблицы соседей, диагоналей и расстояний до края, общие для досок этого размера.
+    geometry: Arc<Geometry>,
+    // Хеш Зобриста текущего расположения камней, обновляется при каждом размещении
+    // и снятии камня.
+    hash: u64,
+    // Стек отмены ходов, сделанных методом play: прежние значения всех измененных ходом
+    // ячеек (размещенный камень, объединенные и захваченные цепочки, соседние цепочки).
+    undo_stack: Vec<UndoEntry>,
+    // Признак записи изменений ячеек в стек отмены (во время выполнения метода play).
+    recording: bool,
 }
 
 impl Board {
-    /// Конструктор.
+    /// Конструктор. Паникует, если размер доски вне диапазона от MIN_BOARD_SIZE до
+    /// MAX_BOARD_SIZE: ссылки между ячейками хранятся в u16, а суммы степеней свободы - в
+    /// u32, и для больших досок они переполняются (см. check_size).
     ///
     /// # Arguments
     ///
@@ -33,22 +129,182 @@ impl Board {
     ///
     /// returns: Board
     pub fn new(num_rows: usize, num_cols: usize) -> Self {
+        if let Err(err) = Board::check_size(num_rows, num_cols) {
+            panic!("{}", err);
+        }
+
+        let stride = num_cols + 2;
+        let mut cells = vec![Cell::new(Content::Border); (num_rows + 2) * stride];
+
+        for row in 1..=num_rows {
+            for col in 1..=num_cols {
+                cells[row * stride + col] = Cell::new(Content::Empty);
+            }
+        }
+
         Board {
             num_rows,
             num_cols,
-            grid: HashMap::new(),
+            stride,
+            cells,
+            geometry: Geometry::for_size(num_rows, num_cols),
+            hash: zobrist::EMPTY_BOARD,
+            undo_stack: Vec::new(),
+            recording: false,
         }
     }
 
+    /// Создает доску по текстовой диаграмме. Каждая непустая строка диаграммы - ряд доски
+    /// (сверху вниз): X - черный камень, O - белый камень, . или + - пустая точка. Пробелы
+    /// между точками допускаются. Диаграмма может содержать обозначения координат в формате
+    /// вывода {:#}: строку с буквами колонок (начинается с A) и номера линий в начале и в
+    /// конце рядов - они пропускаются.
+    ///
+    /// # Arguments
     ///
+    /// * `diagram`: Текстовая диаграмма доски.
+    ///
+    /// Returns: Result<Board, FatalError>
+    pub fn from_diagram(diagram: &str) -> Result<Self, FatalError> {
+        let mut rows: Vec<Vec<Option<Color>>> = Vec::new();
+
+        for line in diagram.lines().map(str::trim) {
+            // Пустые строки и строка с обозначениями колонок.
+            if line.is_empty() || line.starts_with('A') {
+                continue;
+            }
+
+            let points = line.trim_matches(|c: char| c.is_ascii_digit() || c.is_whitespace());
+            let mut row = Vec::new();
+
+            for symbol in points.chars().filter(|c| !c.is_whitespace()) {
+                let point = match symbol {
+                    'X' => { Some(Color::Black) }
+                    'O' => { Some(Color::White) }
+                    '.' | '+' => { None }
+                    _ => {
+                        let err = FatalError::new(format!(
+                            "Недопустимый символ '{}' в строке диаграммы \"{}\"", symbol, line
+                        ));
+
+                        return Err(err);
+                    }
+                };
+
+                row.push(point);
+            }
+
+            rows.push(row);
+        }
+
+        let num_rows = rows.len();
+        let num_cols = rows.first().map_or(0, |row| row.len());
+        Board::check_size(num_rows, num_cols)?;
+
+        if let Some(row) = rows.iter().position(|row| row.len() != num_cols) {
+            let err = FatalError::new(format!(
+                "Ряд {} диаграммы содержит {} точек вместо {}", row + 1, rows[row].len(), num_cols
+            ));
+
+            return Err(err);
+        }
+
+        let mut board = Board::new(num_rows, num_cols);
+        let stones = rows.iter().enumerate().flat_map(|(row, points)| {
+            points.iter().enumerate().filter_map(move |(col, color)| {
+                color.map(|color| (color, Point::new(row + 1, col + 1)))
+            })
+        });
+
+        for stone in stones {
+            board.place_stone(stone).map_err(FatalError::from)?;
+        }
+
+        // Цепочки без степеней свободы снимаются с доски при размещении камней, и доска
+        // перестает совпадать с диаграммой.
+        let captured = rows.iter().enumerate().any(|(row, points)| {
+            points.iter().enumerate().any(|(col, &color)| {
+                board.get_color(&Point::new(row + 1, col + 1)) != color
+            })
+        });
+
+        if captured {
+            let err = FatalError::new("Диаграмма содержит цепочки камней без степеней свободы".to_string());
+
+            return Err(err);
+        }
+
+        Ok(board)
+    }
+
+    /// Число строк игрового поля.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Число колонок игрового поля.
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// Таблицы геометрии доски: соседние и диагональные точки в пределах доски, расстояние
+    /// до края и класс точки.
+    pub fn geometry(&self) -> &Geometry {
+        &self.geometry
+    }
+
+    /// Возвращает хеш Зобриста текущего расположения камней на доске. Одинаковые
+    /// расположения камней имеют одинаковый хеш.
+    pub fn zobrist_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Возвращает цвет камня в точке доски, или None, если точка пуста или находится
+    /// за границами сетки доски. В отличие от get_go_string не собирает цепочку камней.
     ///
     /// # Arguments
     ///
-    /// * `point`:
+    /// * `point`: Точка на доске.
     ///
-    /// returns: Option<&Rc<GoString>>
-    pub fn get_go_string(&self, point: &Point) -> Option<&Rc<GoString>> {
-        self.grid.get(point)
+    /// returns: Option<Color>
+    pub fn get_color(&self, point: &Point) -> Option<Color> {
+        if !self.is_on_grid(point) {
+            return None;
+        }
+
+        match self.cells[self.index(point)].content {
+            Content::Stone(color) => { Some(color) }
+            _ => { None }
+        }
+    }
+
+    /// Возвращает цепочку камней, которой принадлежит камень в переданной точке, или None,
+    /// если точка пуста. Цепочка собирается обходом камней, поэтому для проверки цвета
+    /// точки следует использовать более быстрый метод get_color.
+    ///
+    /// # Arguments
+    ///
+    /// * `point`: Точка на доске.
+    ///
+    /// returns: Option<GoString>
+    pub fn get_go_string(&self, point: &Point) -> Option<GoString> {
+        let color = self.get_color(point)?;
+
+        let mut stones: Vec<Point> = Vec::new();
+        let mut liberties: Vec<Point> = Vec::new();
+
+        for idx in self.string_stones(self.index(point)) {
+            stones.push(self.point(idx));
+
+            for neighbor in self.neighbors(idx) {
+                if self.cells[neighbor].content == Content::Empty {
+                    liberties.push(self.point(neighbor));
+                }
+            }
+        }
+
+        // Повторяющиеся степени свободы отбрасываются множеством внутри цепочки.
+        Some(GoString::new(color, stones, liberties))
     }
 
     /// Размещение камня на доске и проверка количества степеней свободы соседних точек.
@@ -57,162 +313,1033 @@ impl Board {
     ///
     /// * `stone`: Размещаемый камень.
     ///
-    /// Returns: ()
-    pub fn place_stone(&mut self, stone: Stone) -> Result<(), FatalError> {
+    /// Returns: Result<Vec<Point>, IllegalMove> - точки камней противника, снятых с доски
+    /// этим ходом
+    pub fn place_stone(&mut self, stone: Stone) -> Result<Vec<Point>, IllegalMove> {
+        // Ход, сделанный этим методом, не отменяется, поэтому история отмены теряет смысл.
+        self.undo_stack.clear();
+
+        let mut captured = Vec::new();
+        self.place(stone, Some(&mut captured))?;
+
+        Ok(captured)
+    }
+
+    /// Размещение камня на доске с возможностью отмены хода методом undo. Прежние значения
+    /// всех изменяемых ходом ячеек (в том числе камней захваченных и объединенных цепочек)
+    /// сохраняются в стек отмены. Стек не освобождает память между ходами, поэтому поиск
+    /// (минимакс, MCTS) может перебирать позиции парами play/undo без выделения памяти и
+    /// без клонирования доски.
+    ///
+    /// # Arguments
+    ///
+    /// * `stone`: Размещаемый камень.
+    ///
+    /// Returns: Result<usize, IllegalMove> - число камней противника, снятых с доски этим ходом
+    pub fn play(&mut self, stone: Stone) -> Result<usize, IllegalMove> {
+        self.undo_stack.push(UndoEntry::Move { hash: self.hash });
+
+        self.recording = true;
+        let result = self.place(stone, None);
+        self.recording = false;
+
+        // Недопустимый ход доску не изменил.
+        if result.is_err() {
+            self.undo_stack.pop();
+        }
+
+        result
+    }
+
+    /// Отменяет последний ход, сделанный методом play. Возвращает false, если отменять
+    /// нечего.
+    pub fn undo(&mut self) -> bool {
+        while let Some(entry) = self.undo_stack.pop() {
+            match entry {
+                UndoEntry::Cell { idx, cell } => {
+                    self.cells[idx as usize] = cell;
+                }
+                UndoEntry::Move { hash } => {
+                    self.hash = hash;
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Размещение камня на доске (общая часть методов place_stone и play).
+    ///
+    /// # Arguments
+    ///
+    /// * `stone`: Размещаемый камень.
+    /// * `captured`: Вектор для точек снятых камней противника (None для play: без выделения памяти).
+    ///
+    /// Returns: Result<usize, IllegalMove> - число камней противника, снятых с доски этим ходом
+    fn place(&mut self, stone: Stone, mut captured: Option<&mut Vec<Point>>) -> Result<usize, IllegalMove> {
         let color = stone.0;
-        let point= stone.1;
+        let point = stone.1;
 
         if !self.is_on_grid(&point) {
+            return Err(IllegalMove::OffBoard(point));
+        }
+
+        let idx = self.index(&point);
+
+        if self.cells[idx].content != Content::Empty {
+            return Err(IllegalMove::Occupied(point));
+        }
+
+        // Из переданной точки создадим цепочку с одним камнем.
+        *self.cell_mut(idx) = Cell {
+            content: Content::Stone(color),
+            head: idx as u16,
+            next: idx as u16,
+            size: 1,
+            ..Cell::new(Content::Empty)
+        };
+
+        // И так, нам передали точку, сначала исследуем ее окружение. Пустые соседние точки
+        // становятся степенями свободы новой цепочки, а соседние цепочки (любого цвета)
+        // теряют степень свободы в точке размещения камня.
+        for neighbor in self.neighbors(idx) {
+            match self.cells[neighbor].content {
+                Content::Empty => { self.add_liberty(idx, neighbor); }
+                Content::Stone(_) => {
+                    let head = self.head(neighbor);
+                    self.remove_liberty(head, idx);
+                }
+                Content::Border => {}
+            }
+        }
+
+        // Объединим все смежные цепочки камней одного цвета, включая только что созданную.
+        for neighbor in self.neighbors(idx) {
+            if self.cells[neighbor].content == Content::Stone(color)
+                && self.head(neighbor) != self.head(idx)
+            {
+                self.merge_strings(self.head(idx), self.head(neighbor));
+            }
+        }
+
+        // Камень появился на доске: обновляем хеш.
+        self.hash ^= zobrist::hash_code(&stone);
+
+        // Удаляем с доски цепочки камней противоположного цвета, у которых не осталось
+        // степеней свободы.
+        let mut num_captured: usize = 0;
+        for neighbor in self.neighbors(idx) {
+            if self.cells[neighbor].content == Content::Stone(color.other()) {
+                let head = self.head(neighbor);
+
+                if self.cells[head].libs == 0 {
+                    if let Some(points) = captured.as_deref_mut() {
+                        points.extend(self.string_stones(head).map(|stone| self.point(stone)));
+                    }
+
+                    num_captured += self.remove_string(head);
+                }
+            }
+        }
+
+        // Если и после захвата у цепочки нет степеней свободы, то это самозахват: цепочка
+        // снимается с доски (самозахват допускается не всеми правилами, проверка
+        // допустимости хода выполняется в игре).
+        let head = self.head(idx);
+        if self.cells[head].libs == 0 {
+            self.remove_string(head);
+        }
+
+        Ok(num_captured)
+    }
+
+    /// Снимает с доски цепочку камней, которой принадлежит камень в переданной точке
+    /// (например, мертвую группу при подсчете очков). Как и place_stone, очищает стек
+    /// отмены ходов.
+    ///
+    /// # Arguments
+    ///
+    /// * `point`: Точка, занятая камнем цепочки.
+    ///
+    /// Returns: usize - число снятых камней (0, если точка пуста)
+    pub fn remove_string_at(&mut self, point: &Point) -> usize {
+        if self.get_color(point).is_none() {
+            return 0;
+        }
+
+        self.undo_stack.clear();
+
+        let head = self.head(self.index(point));
+        self.remove_string(head)
+    }
+
+    /// Проверяет, что размеры доски лежат в диапазоне от MIN_BOARD_SIZE до MAX_BOARD_SIZE.
+    /// Этот диапазон - единственное ограничение размера, которое требуется плоскому массиву
+    /// ячеек доски, его проверяют все конструкторы доски.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_rows`: Число строк игрового поля.
+    /// * `num_cols`: Число колонок игрового поля.
+    ///
+    /// Returns: Result<(), FatalError>
+    pub fn check_size(num_rows: usize, num_cols: usize) -> Result<(), FatalError> {
+        let range = MIN_BOARD_SIZE..=MAX_BOARD_SIZE;
+
+        if !range.contains(&num_rows) || !range.contains(&num_cols) {
             let err = FatalError::new(format!(
-                "Точка размещения камня ({}, {}) находится за границами сетки доски ({}, {})",
-                point.row,
-                point.col,
-                self.num_rows,
-                self.num_cols,
+                "Размер доски {}x{}: число строк и колонок должно быть от {} до {}",
+                num_rows, num_cols, MIN_BOARD_SIZE, MAX_BOARD_SIZE,
             ));
 
             return Err(err);
         }
 
-        if let Some(_) = self.grid.get(&point) {
-            let err = FatalError::new(format!(
-                "В точке ({}, {}) уже находится камень принадлежащей цепочке камней",
-                point.row,
-                point.col,
-            ));
+        Ok(())
+    }
 
-            return Err(err)
+    /// Метод проверяет, попадает ли переданная точка в границы сетки доски.
+    ///
+    /// # Arguments
+    ///
+    /// * `point`: Точка на доске.
+    ///
+    /// returns: bool
+    pub fn is_on_grid(&self, point: &Point) -> bool {
+        (1 <= point.row) && (point.row <= self.num_rows)
+            && (1 <= point.col) && (point.col <= self.num_cols)
+    }
+
+    /// Метод определяет, приведет ли размещение камня в пустую точку к самозахвату, не
+    /// изменяя доску. Ход не является самозахватом, если у камня есть свободная соседняя
+    /// точка, если он присоединяется к своей цепочке, у которой останутся степени свободы,
+    /// или если он захватывает цепочку противника. Для точки за границами сетки доски
+    /// возвращает false.
+    ///
+    /// # Arguments
+    ///
+    /// * `stone`: Размещаемый камень.
+    ///
+    /// Returns: bool
+    pub fn is_self_capture(&self, stone: Stone) -> bool {
+        let (color, point) = stone;
+
+        if !self.is_on_grid(&point) {
+            return false;
         }
 
-        // Заведем переменные (adjacent - примыкающий, соседний).
-        // Степени свободы данной точки.
-        let mut liberties: Vec<Point> = Vec::new();
+        for neighbor in self.neighbors(self.index(&point)) {
+            match self.cells[neighbor].content {
+                Content::Empty => { return false; }
+                Content::Stone(neighbor_color) => {
+                    let in_atari = self.is_in_atari(self.head(neighbor));
 
-        // Цепочки камней, которые прилегают к размещаемому камню,
-        // с одинаковым с ним цветом.
-        let mut adjacent_same_color: Vec<Rc<GoString>> = Vec::new();
-
-        // Цепочки камней, прилегающие к переданному камню,
-        // с противоположным цветом.
-        let mut adjacent_opposite_color: Vec<Rc<GoString>> = Vec::new();
-
-        // Замыкание для поиска уже добавленной цепочки в вектор. Цепочки сравниваются
-        // на равенство поскольку реализуют типаж PartialEq.
-        let string_search = |haystack: &Vec<Rc<GoString>>, needle: &GoString| -> bool {
-            let mut found = false;
-            for sample in haystack {
-                if (*sample).as_ref() == needle {
-                    found = true;
+                    // Своя цепочка теряет одну степень свободы (точку размещения камня),
+                    // а цепочка противника в атари будет захвачена.
+                    if (neighbor_color == color) != in_atari {
+                        return false;
+                    }
                 }
+                Content::Border => {}
             }
+        }
+
+        true
+    }
+
+    /// Метод определяет, окажется ли цепочка, в которую войдет камень, размещенный в пустую
+    /// точку, в атари (с ровно одной степенью свободы), не изменяя доску. Учитываются
+    /// степени свободы, освобождаемые захватом соседних цепочек противника: каждый снятый
+    /// камень, соседний с получившейся цепочкой, становится ее степенью свободы.
+    ///
+    /// # Arguments
+    ///
+    /// * `stone`: Размещаемый камень.
+    ///
+    /// Returns: bool
+    pub fn is_self_atari(&self, stone: Stone) -> bool {
+        let (color, point) = stone;
+
+        if !self.is_on_grid(&point) {
+            return false;
+        }
+
+        // Камни цепочки после хода, ее степени свободы и снятые камни противника.
+        let mut stones: HashSet<Point> = HashSet::from([point]);
+        let mut liberties: HashSet<Point> = HashSet::new();
+        let mut captured: HashSet<Point> = HashSet::new();
+
+        for &neighbor in self.geometry.neighbors(&point) {
+            match self.get_color(&neighbor) {
+                None => { liberties.insert(neighbor); }
+                Some(neighbor_color) => {
+                    let string = self.get_go_string(&neighbor).unwrap();
+
+                    if neighbor_color == color {
+                        stones.extend(string.get_stones());
+                        liberties.extend(string.get_liberties());
+                    } else if string.num_liberties() == 1 {
+                        captured.extend(string.get_stones());
+                    }
+                }
+            }
+        }
+
+        for stone in captured {
+            if self.geometry.neighbors(&stone).iter().any(|neighbor| stones.contains(neighbor)) {
+                liberties.insert(stone);
+            }
+        }
+
+        liberties.remove(&point);
+
+        liberties.len() == 1
+    }
+
+    /// Вычисляет хеш Зобриста доски, которая получится после размещения камня в пустую
+    /// точку, не изменяя доску (с учетом снятия захваченных камней противника, а при
+    /// самозахвате - снятия своей цепочки). Для точки за границами сетки доски возвращает
+    /// текущий хеш.
+    ///
+    /// # Arguments
+    ///
+    /// * `stone`: Размещаемый камень.
+    ///
+    /// Returns: u64
+    pub fn hash_after_move(&self, stone: Stone) -> u64 {
+        let (color, point) = stone;
 
-            found
+        if !self.is_on_grid(&point) {
+            return self.hash;
+        }
+
+        // При самозахвате снимаются размещенный камень и все соседние цепочки своего цвета
+        // (каждая из них в атари), иначе - соседние цепочки противника в атари.
+        let (victim, mut hash) = if self.is_self_capture(stone) {
+            (color, self.hash)
+        } else {
+            (color.other(), self.hash ^ zobrist::hash_code(&stone))
         };
 
-        // И так, нам передали точку, сначала исследуем ее окружение.
-        for neighbor in point.neighbors() {
-            if !self.is_on_grid(&neighbor) {
+        // Головы снимаемых цепочек (без повторов).
+        let mut captured: [usize; 4] = [0; 4];
+        let mut num_captured = 0;
+
+        for neighbor in self.neighbors(self.index(&point)) {
+            if self.cells[neighbor].content != Content::Stone(victim) {
                 continue;
             }
 
-            match self.grid.get(&neighbor) {
-                // Если соседняя точка не занята цепочкой, увеличиваем свободы.
-                None => {
-                    liberties.push(neighbor);
-                }
-                Some(string) => {
-                    // Если соседняя точка принадлежит цепочке,
-                    // нужно разобрать эти цепочки по цвету.
-                    if string.get_color() == color {
-                        if !string_search(&adjacent_same_color, string) {
-                            adjacent_same_color.push(string.clone());
-                        }
-                    } else {
-                        if !string_search(&adjacent_opposite_color, string) {
-                            adjacent_opposite_color.push(string.clone());
-                        }
-                    }
-                }
+            let head = self.head(neighbor);
+            if self.is_in_atari(head) && !captured[..num_captured].contains(&head) {
+                captured[num_captured] = head;
+                num_captured += 1;
             }
         }
 
-        // Из переданной точки создадим цепочку с одним камнем.
-        let mut new_string = GoString::new(color, vec![point], liberties);
+        for &head in &captured[..num_captured] {
+            for idx in self.string_stones(head) {
+                hash ^= zobrist::hash_code(&(victim, self.point(idx)));
+            }
+        }
+
+        hash
+    }
+
+    /// Определяет, является ли пустая точка глазом игрока (по правилу книги "Deep Learning
+    /// and the Game of Go"). Все соседние точки должны быть заняты камнями игрока. Глаз
+    /// настоящий, если игрок контролирует не менее трех из четырех диагональных точек, а на
+    /// краю и в углу доски - все диагональные точки в пределах доски.
+    ///
+    /// # Arguments
+    ///
+    /// * `point`: Точка на доске.
+    /// * `color`: Цвет игрока.
+    ///
+    /// Returns: Option<EyeKind> - None, если точка не является глазом игрока
+    pub fn is_point_an_eye(&self, point: &Point, color: Color) -> Option<EyeKind> {
+        if !self.is_on_grid(point) {
+            return None;
+        }
 
-        // Объединим все смежные цепочки камней одного вида, включая только что созданную.
-        // Т.к. переменная adjacent_same_color нам больше не нужна, потребляем ее в цикле.
-        for same_color_string in adjacent_same_color {
-            new_string.merged_with(same_color_string.as_ref());
+        let idx = self.index(point);
+        if self.cells[idx].content != Content::Empty {
+            return None;
         }
 
-        let new_string_rc = Rc::new(new_string);
+        // Все соседние точки в пределах доски должны быть заняты камнями игрока.
+        for neighbor in self.neighbors(idx) {
+            match self.cells[neighbor].content {
+                Content::Stone(neighbor_color) if neighbor_color == color => {}
+                Content::Border => {}
+                _ => { return None; }
+            }
+        }
+
+        // Диагональные точки, контролируемые игроком, и диагонали за пределами доски.
+        let mut friendly_corners = 0;
+        let mut off_board_corners = 0;
+        for corner in self.diagonals(idx) {
+            match self.cells[corner].content {
+                Content::Stone(corner_color) if corner_color == color => { friendly_corners += 1; }
+                Content::Border => { off_board_corners += 1; }
+                _ => {}
+            }
+        }
+
+        let is_true = if off_board_corners > 0 {
+            // Точка на краю или в углу: нужны все диагонали в пределах доски.
+            off_board_corners + friendly_corners == 4
+        } else {
+            // Точка в середине доски: достаточно трех диагоналей из четырех.
+            friendly_corners >= 3
+        };
+
+        if is_true {
+            Some(EyeKind::True)
+        } else {
+            Some(EyeKind::False)
+        }
+    }
+
+    /// Находит безусловно живые цепочки камней игрока (алгоритм Бенсона) и принадлежащие
+    /// им области.
+    ///
+    /// # Arguments
+    ///
+    /// * `color`: Цвет игрока.
+    ///
+    /// Returns: UnconditionalLife
+    pub fn unconditionally_alive(&self, color: Color) -> UnconditionalLife {
+        benson::unconditional_life(self, color)
+    }
+
+    /// Индекс ячейки точки в массиве ячеек.
+    fn index(&self, point: &Point) -> usize {
+        point.row * self.stride + point.col
+    }
+
+    /// Точка, соответствующая индексу ячейки.
+    fn point(&self, idx: usize) -> Point {
+        Point::new(idx / self.stride, idx % self.stride)
+    }
+
+    /// Индексы четырех соседних ячеек. Благодаря рамке они существуют для любой
+    /// точки игрового поля.
+    fn neighbors(&self, idx: usize) -> [usize; 4] {
+        [idx - 1, idx + 1, idx - self.stride, idx + self.stride]
+    }
+
+    /// Индексы четырех диагональных ячеек. Благодаря рамке они существуют для любой
+    /// точки игрового поля.
+    fn diagonals(&self, idx: usize) -> [usize; 4] {
+        [
+            idx - self.stride - 1,
+            idx - self.stride + 1,
+            idx + self.stride - 1,
+            idx + self.stride + 1,
+        ]
+    }
+
+    /// Индекс головы цепочки, которой принадлежит камень.
+    fn head(&self, idx: usize) -> usize {
+        self.cells[idx].head as usize
+    }
+
+    /// Итератор по индексам камней цепочки (обход кольцевого списка).
+    fn string_stones(&self, idx: usize) -> StringStones<'_> {
+        StringStones {
+            board: self,
+            start: idx,
+            current: Some(idx),
+        }
+    }
+
+    /// Проверяет, что у цепочки ровно одна степень свободы. Все псевдо-свободы совпадают
+    /// тогда и только тогда, когда libs * lib_sum_sq == lib_sum^2.
+    fn is_in_atari(&self, head: usize) -> bool {
+        let cell = &self.cells[head];
+        let sum = cell.lib_sum as u64;
+
+        cell.libs > 0 && (cell.libs as u64) * (cell.lib_sum_sq as u64) == sum * sum
+    }
 
-        // Ко всем точкам образующих данную объединенную цепочку, привяжем ее саму.
-        for p in new_string_rc.get_stones2() {
-            self.grid.insert(p.clone(), new_string_rc.clone());
+    /// Ячейка для изменения. Во время хода, сделанного методом play, прежнее значение
+    /// ячейки сохраняется в стек отмены.
+    fn cell_mut(&mut self, idx: usize) -> &mut Cell {
+        if self.recording {
+            self.undo_stack.push(UndoEntry::Cell { idx: idx as u16, cell: self.cells[idx] });
         }
 
-        // Уменьшим количества степеней свободы соседних цепочек камней противоположного цвета.
-        // Поскольку цепочки на доске не изменяемы... Клонируем цепочку и удаляем переданную
-        // точку из свобод цепочки. Затем клонированную цепочку снова размещаем на доске.
-        for opposite_color_string in &adjacent_opposite_color {
-            let mut string: GoString = opposite_color_string.as_ref().clone();
-            string.remove_liberty(&point);
+        &mut self.cells[idx]
+    }
+
+    fn add_liberty(&mut self, head: usize, liberty: usize) {
+        let cell = self.cell_mut(head);
+        cell.libs += 1;
+        cell.lib_sum += liberty as u32;
+        cell.lib_sum_sq += (liberty * liberty) as u32;
+    }
+
+    fn remove_liberty(&mut self, head: usize, liberty: usize) {
+        let cell = self.cell_mut(head);
+        cell.libs -= 1;
+        cell.lib_sum -= liberty as u32;
+        cell.lib_sum_sq -= (liberty * liberty) as u32;
+    }
+
+    /// Объединение двух цепочек камней. Камни меньшей цепочки переназначаются на голову
+    /// большей, а кольцевые списки камней сшиваются.
+    fn merge_strings(&mut self, first: usize, second: usize) {
+        let (head, other) = if self.cells[first].size >= self.cells[second].size {
+            (first, second)
+        } else {
+            (second, first)
+        };
 
-            // Если размещения камня приводит к тому, что у цепочки не остается степеней свободы, то
-            // удаляем с доски цепочки камней противоположного цвета с нулевой степенью свободы.
-            if string.num_liberties() == 0 {
-                self.remove_string(&string)
-            } else {
-                self.insert_string(string);
+        let mut idx = other;
+        loop {
+            self.cell_mut(idx).head = head as u16;
+            idx = self.cells[idx].next as usize;
+
+            if idx == other {
+                break;
             }
         }
 
-        Ok(())
+        let head_next = self.cells[head].next;
+        self.cell_mut(head).next = self.cells[other].next;
+        self.cell_mut(other).next = head_next;
+
+        let other_cell = self.cells[other];
+        let cell = self.cell_mut(head);
+        cell.size += other_cell.size;
+        cell.libs += other_cell.libs;
+        cell.lib_sum += other_cell.lib_sum;
+        cell.lib_sum_sq += other_cell.lib_sum_sq;
     }
 
-    /// Метод проверяет, попадает ли переданная точка в границы сетки доски.
+    /// Удаление цепочки камней с доски (учитываются также случаи, когда удаление цепочки может
+    /// привести к увеличению степеней свободы других цепочек).
     ///
     /// # Arguments
     ///
-    /// * `point`: Точка на доске.
+    /// * `head`: Индекс головы удаляемой цепочки камней.
     ///
-    /// returns: bool
-    fn is_on_grid(&self, point: &Point) -> bool {
-        (1 <= point.row) && (point.row <= self.num_rows)
-            && (1 <= point.col) && (point.col <= self.num_cols)
+    /// Returns: usize - число снятых с доски камней
+    fn remove_string(&mut self, head: usize) -> usize {
+        let color = match self.cells[head].content {
+            Content::Stone(color) => { color }
+            _ => { return 0; }
+        };
+
+        // Сначала освобождаем точки, занятые камнями цепочки, и убираем камни из хеша.
+        // Ссылки кольцевого списка сохраняются до конца удаления.
+        let mut idx = head;
+        loop {
+            self.cell_mut(idx).content = Content::Empty;
+            self.hash ^= zobrist::hash_code(&(color, self.point(idx)));
+            idx = self.cells[idx].next as usize;
+
+            if idx == head {
+                break;
+            }
+        }
+
+        // Затем каждая соседняя цепочка (другого цвета, камни того же цвета принадлежали бы
+        // удаляемой цепочке) получает освободившиеся точки в качестве степеней свободы.
+        let mut removed: usize = 0;
+        loop {
+            for neighbor in self.neighbors(idx) {
+                if let Content::Stone(_) = self.cells[neighbor].content {
+                    let neighbor_head = self.head(neighbor);
+                    self.add_liberty(neighbor_head, idx);
+                }
+            }
+
+            removed += 1;
+            idx = self.cells[idx].next as usize;
+
+            if idx == head {
+                break;
+            }
+        }
+
+        removed
+    }
+}
+
+/// Доски равны, если совпадают их размеры и расположение камней (внутреннее устройство
+/// цепочек, например выбор головы цепочки, не учитывается).
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        self.num_rows == other.num_rows
+            && self.num_cols == other.num_cols
+            && self.cells.iter().zip(other.cells.iter()).all(|(a, b)| a.content == b.content)
+    }
+}
+
+/// Вывод доски текстовой диаграммой в формате Board::from_diagram: X - черный камень, O -
+/// белый камень, . - пустая точка. Альтернативная форма ({:#}) добавляет обозначения
+/// колонок (буквы) и линий (номера снизу вверх, как в протоколе GTP).
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coordinates = f.alternate();
+
+        if coordinates {
+            let letters: Vec<String> = COLUMN_LETTERS.chars()
+                .take(self.num_cols)
+                .map(String::from)
+                .collect();
+
+            writeln!(f, "   {}", letters.join(" "))?;
+        }
+
+        for row in 1..=self.num_rows {
+            let line = self.num_rows + 1 - row;
+
+            if coordinates {
+                write!(f, "{:>2} ", line)?;
+            }
+
+            let points: Vec<&str> = (1..=self.num_cols)
+                .map(|col| match self.get_color(&Point::new(row, col)) {
+                    Some(Color::Black) => { "X" }
+                    Some(Color::White) => { "O" }
+                    None => { "." }
+                })
+                .collect();
+
+            write!(f, "{}", points.join(" "))?;
+
+            if coordinates {
+                write!(f, " {}", line)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Итератор по индексам камней цепочки.
+struct StringStones<'a> {
+    board: &'a Board,
+    start: usize,
+    current: Option<usize>,
+}
+
+impl<'a> Iterator for StringStones<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let idx = self.current?;
+
+        let next = self.board.cells[idx].next as usize;
+        self.current = if next == self.start { None } else { Some(next) };
+
+        Some(idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlgo::gotypes::Color;
+
+    /// Захват одиночного камня: камень снимается с доски, а соседние цепочки получают
+    /// освободившуюся точку в качестве степени свободы.
+    #[test]
+    fn capture_single_stone() {
+        let mut board = Board::new(19, 19);
+
+        board.place_stone((Color::White, Point::new(2, 2))).unwrap();
+        board.place_stone((Color::Black, Point::new(1, 2))).unwrap();
+        board.place_stone((Color::Black, Point::new(2, 1))).unwrap();
+        board.place_stone((Color::Black, Point::new(2, 3))).unwrap();
+
+        let captured = board.place_stone((Color::Black, Point::new(3, 2))).unwrap();
+
+        assert_eq!(captured, [Point::new(2, 2)]);
+        assert!(board.get_go_string(&Point::new(2, 2)).is_none());
+
+        for point in [Point::new(1, 2), Point::new(2, 1), Point::new(2, 3), Point::new(3, 2)] {
+            let string = board.get_go_string(&point).unwrap();
+            assert_eq!(string.get_color(), Color::Black);
+            assert_eq!(string.get_stones2().len(), 1);
+        }
+
+        // Камень (3, 2) имеет 4 степени свободы, включая освободившуюся точку (2, 2).
+        assert_eq!(board.get_go_string(&Point::new(3, 2)).unwrap().num_liberties(), 4);
+        // Угловой камень (1, 2): точки (1, 1), (1, 3) и (2, 2).
+        assert_eq!(board.get_go_string(&Point::new(1, 2)).unwrap().num_liberties(), 3);
+    }
+
+    /// Захват цепочки из двух камней в углу доски.
+    #[test]
+    fn capture_string_in_corner() {
+        let mut board = Board::new(9, 9);
+
+        board.place_stone((Color::White, Point::new(1, 1))).unwrap();
+        board.place_stone((Color::White, Point::new(1, 2))).unwrap();
+        board.place_stone((Color::Black, Point::new(2, 1))).unwrap();
+        board.place_stone((Color::Black, Point::new(2, 2))).unwrap();
+
+        let mut captured = board.place_stone((Color::Black, Point::new(1, 3))).unwrap();
+        captured.sort_by_key(|point| point.col);
+
+        assert_eq!(captured, [Point::new(1, 1), Point::new(1, 2)]);
+        assert!(board.get_go_string(&Point::new(1, 1)).is_none());
+        assert!(board.get_go_string(&Point::new(1, 2)).is_none());
+
+        // Черные камни (2, 1) и (2, 2) связаны в одну цепочку и получили точки (1, 1) и (1, 2).
+        let string = board.get_go_string(&Point::new(2, 1)).unwrap();
+        assert_eq!(string.get_stones2().len(), 2);
+        assert_eq!(string.num_liberties(), 5);
+    }
+
+    /// Ход без захвата не снимает камней.
+    #[test]
+    fn place_stone_without_capture() {
+        let mut board = Board::new(9, 9);
+
+        assert!(board.place_stone((Color::Black, Point::new(5, 5))).unwrap().is_empty());
+        assert!(board.place_stone((Color::White, Point::new(5, 6))).unwrap().is_empty());
+        assert_eq!(board.get_go_string(&Point::new(5, 5)).unwrap().num_liberties(), 3);
+        assert_eq!(board.get_go_string(&Point::new(5, 6)).unwrap().num_liberties(), 3);
+    }
+
+    /// Доска недопустимого размера не создается.
+    #[test]
+    #[should_panic(expected = "Размер доски 300x300")]
+    fn reject_oversized_board() {
+        Board::new(300, 300);
+    }
+
+    /// Доска, построенная по диаграмме, выводится той же диаграммой.
+    #[test]
+    fn diagram_round_trip() {
+        let board = Board::from_diagram("
+            . X O . .
+            X O . O .
+            . X O . .
+        ").unwrap();
+
+        assert_eq!((board.num_rows(), board.num_cols()), (3, 5));
+        assert_eq!(board.get_color(&Point::new(1, 2)), Some(Color::Black));
+        assert_eq!(board.get_color(&Point::new(2, 2)), Some(Color::White));
+        assert_eq!(board.get_go_string(&Point::new(2, 2)).unwrap().num_liberties(), 1);
+
+        let plain = board.to_string();
+        assert_eq!(plain, ". X O . .\nX O . O .\n. X O . .\n");
+        assert!(Board::from_diagram(&plain).unwrap() == board);
+
+        // Диаграмма с координатами читается так же.
+        let with_coordinates = format!("{:#}", board);
+        assert_eq!(with_coordinates, "   A B C D E\n 3 . X O . . 3\n 2 X O . O . 2\n 1 . X O . . 1\n");
+        assert!(Board::from_diagram(&with_coordinates).unwrap() == board);
+
+        // Ряды разной длины, неизвестные символы и цепочки без степеней свободы отклоняются.
+        assert!(Board::from_diagram("X . .\n. .").is_err());
+        assert!(Board::from_diagram("X . ?\n. . .").is_err());
+        assert!(Board::from_diagram("X O\nO .").is_err());
+        assert!(Board::from_diagram(&format!("{0}\n{0}", ". ".repeat(26))).is_err());
+    }
+
+    /// Глаза в середине, на краю и в углу доски, настоящие и ложные.
+    #[test]
+    fn eye_detection() {
+        let place = |board: &mut Board, color: Color, points: &[(usize, usize)]| {
+            for &(row, col) in points {
+                board.place_stone((color, Point::new(row, col))).unwrap();
+            }
+        };
+
+        // Глаз в углу (1, 1) и глаз на краю (1, 4).
+        let mut board = Board::new(9, 9);
+        place(&mut board, Color::Black, &[(1, 2), (2, 1), (2, 2), (1, 3), (1, 5), (2, 3), (2, 4), (2, 5)]);
+        assert_eq!(board.is_point_an_eye(&Point::new(1, 1), Color::Black), Some(EyeKind::True));
+        assert_eq!(board.is_point_an_eye(&Point::new(1, 4), Color::Black), Some(EyeKind::True));
+        assert_eq!(board.is_point_an_eye(&Point::new(1, 1), Color::White), None);
+        assert_eq!(board.is_point_an_eye(&Point::new(1, 2), Color::Black), None);
+
+        // На краю доски глаз ложный, если противник занимает диагональную точку.
+        let mut edge = Board::new(9, 9);
+        place(&mut edge, Color::Black, &[(1, 3), (1, 5), (2, 4)]);
+        place(&mut edge, Color::White, &[(2, 5)]);
+        assert_eq!(edge.is_point_an_eye(&Point::new(1, 4), Color::Black), Some(EyeKind::False));
+
+        // В середине доски глаз настоящий при трех своих диагоналях из четырех
+        // и ложный при двух.
+        let mut center = Board::new(9, 9);
+        place(&mut center, Color::Black, &[(4, 5), (6, 5), (5, 4), (5, 6), (4, 4), (4, 6)]);
+        place(&mut center, Color::White, &[(6, 6)]);
+        let eye = Point::new(5, 5);
+        assert_eq!(center.is_point_an_eye(&eye, Color::Black), Some(EyeKind::False));
+        place(&mut center, Color::Black, &[(6, 4)]);
+        assert_eq!(center.is_point_an_eye(&eye, Color::Black), Some(EyeKind::True));
+    }
+
+    /// Хеш Зобриста обновляется при размещении и снятии камней и зависит только от
+    /// расположения камней.
+    #[test]
+    fn zobrist_hash_follows_captures() {
+        let mut board = Board::new(9, 9);
+        assert_eq!(board.zobrist_hash(), zobrist::EMPTY_BOARD);
+
+        board.place_stone((Color::White, Point::new(1, 1))).unwrap();
+        board.place_stone((Color::Black, Point::new(1, 2))).unwrap();
+        assert_ne!(board.zobrist_hash(), zobrist::EMPTY_BOARD);
+
+        board.place_stone((Color::Black, Point::new(2, 1))).unwrap();
+
+        // Та же позиция, полученная без захвата.
+        let mut expected = Board::new(9, 9);
+        expected.place_stone((Color::Black, Point::new(2, 1))).unwrap();
+        expected.place_stone((Color::Black, Point::new(1, 2))).unwrap();
+
+        assert_eq!(board.zobrist_hash(), expected.zobrist_hash());
+        assert!(board == expected);
+    }
+
+    /// Проверка самозахвата и хеша после хода без изменения доски совпадает с результатом
+    /// реального размещения камня.
+    #[test]
+    fn probe_move_without_placing() {
+        let mut board = Board::new(9, 9);
+        board.place_stone((Color::White, Point::new(1, 1))).unwrap();
+        board.place_stone((Color::Black, Point::new(1, 2))).unwrap();
+        board.place_stone((Color::Black, Point::new(3, 1))).unwrap();
+        board.place_stone((Color::White, Point::new(3, 2))).unwrap();
+        board.place_stone((Color::White, Point::new(4, 1))).unwrap();
+
+        // Черные в (2, 1) захватывают угловой камень, хотя сами оказываются без свобод
+        // до снятия камня.
+        let stone = (Color::Black, Point::new(2, 1));
+        assert!(!board.is_self_capture(stone));
+
+        let mut next = board.clone();
+        next.place_stone(stone).unwrap();
+        assert_eq!(board.hash_after_move(stone), next.zobrist_hash());
+
+        // Белые в (2, 1) — самозахват: точка окружена, захвата нет.
+        let mut corner = Board::new(9, 9);
+        corner.place_stone((Color::Black, Point::new(1, 2))).unwrap();
+        corner.place_stone((Color::Black, Point::new(2, 1))).unwrap();
+        assert!(corner.is_self_capture((Color::White, Point::new(1, 1))));
+        assert!(!corner.is_self_capture((Color::Black, Point::new(1, 1))));
+
+        // Точки за границами сетки доски не вызывают панику.
+        for point in [Point::new(0, 0), Point::new(20, 20)] {
+            assert!(!board.is_self_capture((Color::Black, point)));
+            assert_eq!(board.hash_after_move((Color::Black, point)), board.zobrist_hash());
+        }
     }
 
-    fn insert_string(&mut self, string: GoString) {
-        let as_rc = Rc::new(string);
+    /// Камни, снятые ходом, становятся степенями свободы цепочки, если касаются любого ее
+    /// камня, а не только размещенного.
+    #[test]
+    fn self_atari_counts_captured_stones() {
+        let board = Board::from_diagram("
+            . O X
+            X O X
+            X X X
+        ").unwrap();
+
+        // Черные снимают два камня и получают степени свободы (1, 2) и (2, 2).
+        assert!(!board.is_self_atari((Color::Black, Point::new(1, 1))));
+        // Белые снимают всю черную цепочку.
+        assert!(!board.is_self_atari((Color::White, Point::new(1, 1))));
+
+        let mut next = board.clone();
+        next.place_stone((Color::Black, Point::new(1, 1))).unwrap();
+        assert_eq!(next.get_go_string(&Point::new(1, 1)).unwrap().num_liberties(), 2);
+    }
 
-        for p in as_rc.get_stones() {
-            // Метод обновляет значения.
-            self.grid.insert(p, as_rc.clone());
+    /// Последняя степень свободы, которой касаются несколько камней цепочки, считается
+    /// один раз: цепочка находится в атари и захватывается.
+    #[test]
+    fn shared_last_liberty() {
+        let mut board = Board::new(9, 9);
+        for point in [Point::new(1, 2), Point::new(2, 1), Point::new(2, 2)] {
+            board.place_stone((Color::White, point)).unwrap();
         }
+        for point in [Point::new(1, 3), Point::new(2, 3), Point::new(3, 1), Point::new(3, 2)] {
+            board.place_stone((Color::Black, point)).unwrap();
+        }
+
+        assert_eq!(board.get_go_string(&Point::new(2, 2)).unwrap().num_liberties(), 1);
+
+        let stone = (Color::Black, Point::new(1, 1));
+        assert!(!board.is_self_capture(stone));
+
+        let expected_hash = board.hash_after_move(stone);
+        assert_eq!(board.place_stone(stone).unwrap().len(), 3);
+        assert_eq!(board.zobrist_hash(), expected_hash);
+        assert_eq!(board.get_go_string(&Point::new(1, 1)).unwrap().num_liberties(), 2);
     }
 
-    fn remove_string(&mut self, string: &GoString) {
-        /*
+    /// Ходы, сделанные методом play, отменяются методом undo в обратном порядке, включая
+    /// захваты и объединения цепочек.
+    #[test]
+    fn play_and_undo_restore_positions() {
+        let mut seed: u64 = 7;
+        let mut next_random = move |bound: usize| -> usize {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((seed >> 33) as usize) % bound
+        };
+
+        let mut board = Board::new(9, 9);
+        let mut history: Vec<Board> = Vec::new();
+        let mut color = Color::Black;
+        let mut captured: usize = 0;
+
+        for _ in 0..1000 {
+            let stone = (color, Point::new(next_random(9) + 1, next_random(9) + 1));
+            if board.get_color(&stone.1).is_some() || board.is_self_capture(stone) {
+                continue;
+            }
+
+            history.push(board.clone());
+            captured += board.play(stone).unwrap();
+            color = color.other();
+        }
 
-    def _remove_string(self, string: GoString) -> None:
-        """
-        Удаление цепочки камней с доски (учитываются также случаи, когда удаление цепочки может привести к
-        увеличению степеней свободы других цепочек).
-        :param string: GoString Удаляемая цепочка камней.
-        """
-        for point in string.stones:
-            for neighbor in point.neighbors():
-                neighbor_string = self._grid.get(neighbor)
+        // Случайная партия на маленькой доске обязательно содержит захваты.
+        assert!(captured > 0);
 
-                if neighbor_string is None:
-                    continue
+        while let Some(expected) = history.pop() {
+            assert!(board.undo());
+            assert!(board == expected);
+            assert_eq!(board.zobrist_hash(), expected.zobrist_hash());
 
-                if neighbor_string is not string:
-                    neighbor_string.add_liberty(point)
+            // Счетчики цепочек восстановлены: собранные цепочки совпадают.
+            for row in 1..=9 {
+                for col in 1..=9 {
+                    let point = Point::new(row, col);
+                    assert_eq!(board.get_go_string(&point), expected.get_go_string(&point));
+                }
+            }
+        }
 
-            self._grid[point] = None
-        */
+        assert!(!board.undo());
     }
 
+    /// Недопустимый ход не попадает в стек отмены, а place_stone очищает стек.
+    #[test]
+    fn undo_stack_bookkeeping() {
+        let mut board = Board::new(9, 9);
+        board.play((Color::Black, Point::new(5, 5))).unwrap();
+        assert_eq!(board.play((Color::White, Point::new(5, 5))), Err(IllegalMove::Occupied(Point::new(5, 5))));
+
+        assert!(board.undo());
+        assert!(board == Board::new(9, 9));
+
+        board.play((Color::Black, Point::new(5, 5))).unwrap();
+        board.place_stone((Color::White, Point::new(4, 4))).unwrap();
+        assert!(!board.undo());
+    }
+
+    /// Самозахват снимает с доски размещенный камень вместе с присоединенной цепочкой.
+    #[test]
+    fn self_capture_removes_own_string() {
+        let mut board = Board::new(9, 9);
+        board.place_stone((Color::White, Point::new(1, 1))).unwrap();
+        for point in [Point::new(1, 3), Point::new(2, 1), Point::new(2, 2)] {
+            board.place_stone((Color::Black, point)).unwrap();
+        }
+
+        let stone = (Color::White, Point::new(1, 2));
+        assert!(board.is_self_capture(stone));
+
+        let expected_hash = board.hash_after_move(stone);
+        assert!(board.place_stone(stone).unwrap().is_empty());
+        assert_eq!(board.zobrist_hash(), expected_hash);
+        assert!(board.get_color(&Point::new(1, 1)).is_none());
+        assert!(board.get_color(&Point::new(1, 2)).is_none());
+        assert_eq!(board.get_go_string(&Point::new(2, 1)).unwrap().num_liberties(), 5);
+    }
+
+    /// Нагрузочный прогон доски: случайные партии на доске 19x19, в которых каждый ход, как
+    /// и в Game::apply_move, делается на клоне доски. Время прогона показывает запуск:
+    /// time cargo test --release -- --ignored benchmark_clone_and_place
+    #[test]
+    #[ignore]
+    fn benchmark_clone_and_place() {
+        // Простой линейный конгруэнтный генератор, чтобы партии были воспроизводимы.
+        let mut seed: u64 = 42;
+        let mut next_random = move |bound: usize| -> usize {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((seed >> 33) as usize) % bound
+        };
+
+        let games = 500;
+        let attempts_per_game = 1000;
+        let mut applied: usize = 0;
+        let mut captured: usize = 0;
+
+        for _ in 0..games {
+            let mut board = Board::new(19, 19);
+            let mut color = Color::Black;
+
+            for _ in 0..attempts_per_game {
+                let stone = (color, Point::new(next_random(19) + 1, next_random(19) + 1));
+                if board.get_color(&stone.1).is_some() || board.is_self_capture(stone) {
+                    continue;
+                }
+
+                let mut next_board = board.clone();
+                captured += next_board.place_stone(stone).unwrap().len();
+                board = next_board;
+
+                applied += 1;
+                color = color.other();
+            }
+        }
+
+        assert!(applied > 0 && captured > 0);
+    }
+
+    /// Нагрузочный прогон перебора ходов парами play/undo (без клонирования доски). Время
+    /// прогона показывает запуск: time cargo test --release -- --ignored benchmark_play_undo
+    #[test]
+    #[ignore]
+    fn benchmark_play_undo() {
+        let mut seed: u64 = 42;
+        let mut next_random = move |bound: usize| -> usize {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((seed >> 33) as usize) % bound
+        };
+
+        // Позиция середины партии.
+        let mut board = Board::new(19, 19);
+        let mut color = Color::Black;
+        for _ in 0..400 {
+            let stone = (color, Point::new(next_random(19) + 1, next_random(19) + 1));
+            if board.get_color(&stone.1).is_none() && !board.is_self_capture(stone) {
+                board.place_stone(stone).unwrap();
+                color = color.other();
+            }
+        }
+
+        let rounds = 20000;
+        let position = board.clone();
+
+        for _ in 0..rounds {
+            for row in 1..=19 {
+                for col in 1..=19 {
+                    let stone = (color, Point::new(row, col));
+                    if board.get_color(&stone.1).is_none() && !board.is_self_capture(stone) {
+                        board.play(stone).unwrap();
+                        board.undo();
+                    }
+                }
+            }
+        }
+
+        assert!(board == position);
+        assert_eq!(board.zobrist_hash(), position.zobrist_hash());
+    }
 }
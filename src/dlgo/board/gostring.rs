@@ -64,6 +64,11 @@ impl GoString {
         &self.stones
     }
 
+    /// Возвращает степени свободы цепочки камней как вектор.
+    pub fn get_liberties(&self) -> Vec<Point> {
+        Vec::from_iter(self.liberties.iter().copied())
+    }
+
     /// Число степеней свободы у данной цепочке камней.
     pub fn num_liberties(&self) -> usize {
         self.liberties.len()
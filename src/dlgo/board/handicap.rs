@@ -0,0 +1,148 @@
+//! Расстановка камней форы.
+//!
+//! При фиксированной форе черные камни ставятся на стандартные пункты (хоси) в порядке,
+//! принятом в протоколе GTP (команда fixed_handicap). При свободной форе черные сами
+//! выбирают пункты. В обоих случаях после расстановки первыми ходят белые.
+
+use std::collections::HashSet;
+use crate::dlgo::error::FatalError;
+use crate::dlgo::gotypes::Point;
+
+/// Наименьшее число камней форы.
+pub const MIN_HANDICAP: usize = 2;
+
+/// Наибольшее число камней фиксированной форы.
+pub const MAX_FIXED_HANDICAP: usize = 9;
+
+/// Возвращает пункты фиксированной форы в порядке протокола GTP.
+///
+/// Фиксированная фора поддерживается на досках 9x9 (пункты на третьей линии), 13x13 и
+/// 19x19 (пункты на четвертой линии).
+///
+/// # Arguments
+///
+/// * `board_size`: Размер доски.
+/// * `stones`: Число камней форы (от 2 до 9).
+///
+/// Returns: Result<Vec<Point>, FatalError>
+pub fn fixed_handicap_points(board_size: usize, stones: usize) -> Result<Vec<Point>, FatalError> {
+    // Расстояние пунктов форы от края доски (номер линии).
+    let line = match board_size {
+        9 => { 3 }
+        13 | 19 => { 4 }
+        _ => {
+            let err = FatalError::new(format!(
+                "Фиксированная фора не поддерживается на доске размера {}", board_size
+            ));
+
+            return Err(err);
+        }
+    };
+
+    if !(MIN_HANDICAP..=MAX_FIXED_HANDICAP).contains(&stones) {
+        let err = FatalError::new(format!(
+            "Фиксированная фора должна быть от {} до {} камней", MIN_HANDICAP, MAX_FIXED_HANDICAP
+        ));
+
+        return Err(err);
+    }
+
+    let low = line;
+    let high = board_size + 1 - line;
+    let mid = board_size / 2 + 1;
+
+    // Пункты в нотации GTP (колонка, линия снизу): D4, Q16, D16, Q4, D10, Q10, K4, K16.
+    // Центральный пункт K10 добавляется при нечетном числе камней.
+    let corners = [(low, low), (high, high), (low, high), (high, low)];
+    let sides = [(low, mid), (high, mid), (mid, low), (mid, high)];
+
+    let mut gtp_points: Vec<(usize, usize)> = Vec::new();
+    gtp_points.extend_from_slice(&corners[..stones.min(4)]);
+    if stones >= 6 {
+        gtp_points.extend_from_slice(&sides[..2]);
+    }
+    if stones >= 8 {
+        gtp_points.extend_from_slice(&sides[2..]);
+    }
+    if stones >= 5 && stones % 2 == 1 {
+        gtp_points.push((mid, mid));
+    }
+
+    // Линии в GTP считаются снизу, а ряды доски - сверху.
+    let points = gtp_points.iter()
+        .map(|&(col, line)| Point::new(board_size + 1 - line, col))
+        .collect();
+
+    Ok(points)
+}
+
+/// Проверяет пункты свободной форы: не меньше двух камней, все пункты на доске и различны,
+/// и на доске остается хотя бы одна свободная точка.
+///
+/// # Arguments
+///
+/// * `board_size`: Размер доски.
+/// * `points`: Пункты, выбранные черными.
+///
+/// Returns: Result<(), FatalError>
+pub fn validate_free_handicap(board_size: usize, points: &[Point]) -> Result<(), FatalError> {
+    if points.len() < MIN_HANDICAP || points.len() >= board_size * board_size {
+        let err = FatalError::new(format!(
+            "Свободная фора должна быть от {} до {} камней", MIN_HANDICAP, board_size * board_size - 1
+        ));
+
+        return Err(err);
+    }
+
+    let mut unique: HashSet<Point> = HashSet::new();
+    for point in points {
+        let on_grid = (1..=board_size).contains(&point.row) && (1..=board_size).contains(&point.col);
+        if !on_grid || !unique.insert(*point) {
+            let err = FatalError::new(format!(
+                "Недопустимый пункт форы ({}, {})", point.row, point.col
+            ));
+
+            return Err(err);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Пункты фиксированной форы на доске 19x19 идут в порядке GTP.
+    #[test]
+    fn fixed_handicap_in_gtp_order() {
+        // D4 Q16 D16 Q4 D10 Q10 K4 K16 K10 в матричной нотации.
+        let expected = [
+            (16, 4), (4, 16), (4, 4), (16, 16), (10, 4), (10, 16), (16, 10), (4, 10), (10, 10),
+        ];
+        let points = fixed_handicap_points(19, 9).unwrap();
+        assert_eq!(points, expected.iter().map(|&(r, c)| Point::new(r, c)).collect::<Vec<_>>());
+
+        // Пять камней: четыре угла и центр; шесть камней: углы и боковые пункты.
+        let five = fixed_handicap_points(19, 5).unwrap();
+        assert_eq!(five[4], Point::new(10, 10));
+        let six = fixed_handicap_points(19, 6).unwrap();
+        assert_eq!(six[4..], [Point::new(10, 4), Point::new(10, 16)]);
+
+        // На доске 9x9 пункты лежат на третьей линии.
+        assert_eq!(fixed_handicap_points(9, 2).unwrap(), [Point::new(7, 3), Point::new(3, 7)]);
+
+        assert!(fixed_handicap_points(19, 1).is_err());
+        assert!(fixed_handicap_points(19, 10).is_err());
+        assert!(fixed_handicap_points(11, 4).is_err());
+    }
+
+    /// Свободная фора отклоняет повторяющиеся пункты и пункты вне доски.
+    #[test]
+    fn free_handicap_validation() {
+        assert!(validate_free_handicap(9, &[Point::new(1, 1), Point::new(9, 9)]).is_ok());
+        assert!(validate_free_handicap(9, &[Point::new(1, 1)]).is_err());
+        assert!(validate_free_handicap(9, &[Point::new(1, 1), Point::new(1, 1)]).is_err());
+        assert!(validate_free_handicap(9, &[Point::new(1, 1), Point::new(10, 1)]).is_err());
+    }
+}
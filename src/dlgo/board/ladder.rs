@@ -0,0 +1,241 @@
+//! Чтение лестниц (сичо).
+//!
+//! Лестница - погоня, в которой атакующий каждым ходом ставит убегающую цепочку в атари,
+//! а убегающий наращивает цепочку в единственную степень свободы. Чтение выполняется на
+//! копии доски ходами Board::play/undo. Убегающий также может выйти из атари, захватив
+//! соседнюю цепочку атакующего. Лестница не работает, если цепочка получает три и более
+//! степени свободы (как правило, соединившись со своим камнем на пути лестницы - ломающим
+//! камнем).
+
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use crate::dlgo::board::goboard::Board;
+use crate::dlgo::board::gostring::GoString;
+use crate::dlgo::gotypes::{Color, Point};
+
+/// Результат чтения лестницы.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub struct Ladder {
+    /// Лестница работает: цепочка будет захвачена.
+    pub works: bool,
+    /// Длина прочитанной погони: число ходов обеих сторон до захвата цепочки (включая
+    /// захватывающий ход) или до ее спасения.
+    pub length: usize,
+    /// Камень, ломающий лестницу: камень цвета цепочки, с которым она соединилась, или
+    /// захваченный ею камень атакующего.
+    pub breaker: Option<Point>,
+}
+
+/// Читает лестницу на цепочку камней. Если цепочка в атари, первым ходит убегающий, если у
+/// цепочки две степени свободы - атакующий.
+///
+/// # Arguments
+///
+/// * `board`: Доска.
+/// * `string`: Цепочка камней с одной или двумя степенями свободы.
+///
+/// Returns: Option<Ladder> - None, если у цепочки больше двух степеней свободы
+pub fn read_ladder(board: &Board, string: &GoString) -> Option<Ladder> {
+    let stones = string.get_stones2();
+    let prey_stone = *stones.iter().next()?;
+
+    let mut reader = Reader {
+        board: board.clone(),
+        prey: string.get_color(),
+        prey_stone,
+        // Камни цвета цепочки, стоявшие на доске до начала погони (кроме самой цепочки).
+        friends: friendly_stones(board, string.get_color(), stones),
+        max_length: 2 * board.num_rows() * board.num_cols(),
+    };
+
+    match string.num_liberties() {
+        1 => { Some(reader.prey_to_move(0)) }
+        2 => { Some(reader.hunter_to_move(0)) }
+        _ => { None }
+    }
+}
+
+/// Состояние чтения лестницы.
+struct Reader {
+    // Копия доски, на которой разыгрывается погоня.
+    board: Board,
+    prey: Color,
+    // Один из камней убегающей цепочки (цепочка только растет, пока не захвачена).
+    prey_stone: Point,
+    friends: HashSet<Point>,
+    // Предел длины погони (защита от бесконечного чтения).
+    max_length: usize,
+}
+
+impl Reader {
+    /// Ход убегающего: цепочка в атари наращивается в степень свободы или захватывает
+    /// соседнюю цепочку атакующего, находящуюся в атари.
+    fn prey_to_move(&mut self, length: usize) -> Ladder {
+        let string = self.board.get_go_string(&self.prey_stone).unwrap();
+
+        // Кандидаты: (ход, захватываемый камень атакующего).
+        let mut candidates: Vec<(Point, Option<Point>)> = sorted(string.get_liberties())
+            .into_iter()
+            .map(|liberty| (liberty, None))
+            .collect();
+
+        for stone in sorted(string.get_stones()) {
+            for &neighbor in self.board.geometry().neighbors(&stone) {
+                if self.board.get_color(&neighbor) != Some(self.prey.other()) {
+                    continue;
+                }
+
+                let hunter = self.board.get_go_string(&neighbor).unwrap();
+                if hunter.num_liberties() == 1 {
+                    let liberty = hunter.get_liberties()[0];
+                    if !candidates.iter().any(|&(point, _)| point == liberty) {
+                        candidates.push((liberty, Some(neighbor)));
+                    }
+                }
+            }
+        }
+
+        // Если спастись не удается, убегающий выбирает самую длинную погоню.
+        let mut best = Ladder { works: true, length: length + 1, breaker: None };
+
+        for (point, captures) in candidates {
+            let stone = (self.prey, point);
+            if self.board.is_self_capture(stone) || self.board.play(stone).is_err() {
+                continue;
+            }
+
+            let string = self.board.get_go_string(&self.prey_stone).unwrap();
+            let outcome = match string.num_liberties() {
+                // Атакующий захватывает цепочку.
+                1 => { Ladder { works: true, length: length + 2, breaker: None } }
+                2 => { self.hunter_to_move(length + 1) }
+                _ => {
+                    let breaker = sorted(string.get_stones()).into_iter()
+                        .find(|stone| self.friends.contains(stone))
+                        .or(captures);
+
+                    Ladder { works: false, length: length + 1, breaker }
+                }
+            };
+
+            self.board.undo();
+
+            if !outcome.works {
+                return outcome;
+            }
+            if outcome.length > best.length {
+                best = outcome;
+            }
+        }
+
+        best
+    }
+
+    /// Ход атакующего: цепочка с двумя степенями свободы ставится в атари одним из
+    /// двух ходов.
+    fn hunter_to_move(&mut self, length: usize) -> Ladder {
+        if length >= self.max_length {
+            return Ladder { works: false, length, breaker: None };
+        }
+
+        let string = self.board.get_go_string(&self.prey_stone).unwrap();
+        let mut escape: Option<Ladder> = None;
+
+        for point in sorted(string.get_liberties()) {
+            let stone = (self.prey.other(), point);
+            if self.board.is_self_capture(stone) || self.board.play(stone).is_err() {
+                continue;
+            }
+
+            let outcome = self.prey_to_move(length + 1);
+            self.board.undo();
+
+            if outcome.works {
+                return outcome;
+            }
+            // Если поймать не удается, результатом считается самая длинная погоня (обычно
+            // именно она доходит до ломающего камня).
+            if escape.is_none_or(|escape| outcome.length > escape.length) {
+                escape = Some(outcome);
+            }
+        }
+
+        escape.unwrap_or(Ladder { works: false, length, breaker: None })
+    }
+}
+
+/// Камни цвета игрока, не принадлежащие цепочке.
+fn friendly_stones(board: &Board, color: Color, string: &HashSet<Point>) -> HashSet<Point> {
+    let mut friends = HashSet::new();
+
+    for row in 1..=board.num_rows() {
+        for col in 1..=board.num_cols() {
+            let point = Point::new(row, col);
+            if board.get_color(&point) == Some(color) && !string.contains(&point) {
+                friends.insert(point);
+            }
+        }
+    }
+
+    friends
+}
+
+/// Упорядочивает точки (ряд, затем колонка), делая чтение детерминированным.
+fn sorted(mut points: Vec<Point>) -> Vec<Point> {
+    points.sort_by_key(|point| (point.row, point.col));
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Доска с белым камнем (3, 3) в атари: лестница идет по диагонали к углу (9, 9).
+    fn ladder_board() -> Board {
+        let mut board = Board::new(9, 9);
+        for point in [(4, 3), (3, 2), (2, 3), (2, 4)] {
+            board.place_stone((Color::Black, Point::new(point.0, point.1))).unwrap();
+        }
+        board.place_stone((Color::White, Point::new(3, 3))).unwrap();
+
+        board
+    }
+
+    /// На пустой доске лестница доходит до края и работает.
+    #[test]
+    fn ladder_works_on_empty_board() {
+        let mut board = ladder_board();
+        let prey = board.get_go_string(&Point::new(3, 3)).unwrap();
+
+        let ladder = read_ladder(&board, &prey).unwrap();
+
+        assert_eq!(ladder, Ladder { works: true, length: 23, breaker: None });
+        // Чтение не изменяет доску.
+        assert_eq!(board.get_color(&Point::new(3, 4)), None);
+
+        // После выхода из атари цепочка с двумя степенями свободы: первым ходит атакующий.
+        board.place_stone((Color::White, Point::new(3, 4))).unwrap();
+        let prey = board.get_go_string(&Point::new(3, 3)).unwrap();
+        assert_eq!(read_ladder(&board, &prey).unwrap().length, 22);
+    }
+
+    /// Белый камень на пути лестницы ломает ее.
+    #[test]
+    fn ladder_breaker() {
+        let mut board = ladder_board();
+        board.place_stone((Color::White, Point::new(7, 7))).unwrap();
+        let prey = board.get_go_string(&Point::new(3, 3)).unwrap();
+
+        let ladder = read_ladder(&board, &prey).unwrap();
+
+        assert!(!ladder.works);
+        assert_eq!(ladder.breaker, Some(Point::new(7, 7)));
+
+        // Цепочка с тремя степенями свободы лестницей не читается.
+        let free = board.get_go_string(&Point::new(7, 7)).unwrap();
+        assert!(read_ladder(&board, &free).is_none());
+    }
+}
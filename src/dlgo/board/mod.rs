@@ -1,3 +1,17 @@
 pub mod gostring;
 pub mod goboard;
-pub mod game;
\ No newline at end of file
+pub mod game;
+pub mod rules;
+pub mod result;
+pub mod scoring;
+pub mod dead_stones;
+pub mod handicap;
+pub mod game_tree;
+pub mod benson;
+pub mod ladder;
+pub mod seki;
+pub mod geometry;
+pub mod symmetry;
+
+#[cfg(test)]
+mod fixtures;
@@ -0,0 +1,81 @@
+//! Результат партии.
+
+#![allow(dead_code)]
+
+use std::fmt::Formatter;
+use crate::dlgo::gotypes::Color;
+
+/// Результат завершенной партии. Отображается в нотации SGF: `B+R`, `W+6.5`, `Draw`, `Void`.
+#[derive(Copy, Clone)]
+#[derive(PartialEq)]
+#[derive(Debug)]
+pub enum GameResult {
+    /// Победа игрока переданного цвета: противник вышел из игры.
+    Resignation(Color),
+    /// Победа игрока переданного цвета по очкам с переданным перевесом.
+    Score(Color, f32),
+    /// Ничья (равный счет с учетом коми).
+    Draw,
+    /// Партия не имеет результата (например, отменена судьей).
+    Void,
+}
+
+impl GameResult {
+    /// Возвращает цвет победителя, или None для ничьей и партии без результата.
+    pub fn winner(&self) -> Option<Color> {
+        match self {
+            GameResult::Resignation(color) | GameResult::Score(color, _) => { Some(*color) }
+            GameResult::Draw | GameResult::Void => { None }
+        }
+    }
+
+    /// Строит результат по разнице очков черных и белых (коми уже учтено).
+    ///
+    /// # Arguments
+    ///
+    /// * `margin`: Очки черных минус очки белых.
+    ///
+    /// Returns: GameResult
+    pub fn from_margin(margin: f32) -> Self {
+        if margin > 0.0 {
+            GameResult::Score(Color::Black, margin)
+        } else if margin < 0.0 {
+            GameResult::Score(Color::White, -margin)
+        } else {
+            GameResult::Draw
+        }
+    }
+}
+
+impl std::fmt::Display for GameResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let color_symbol = |color: &Color| match color {
+            Color::Black => { "B" }
+            Color::White => { "W" }
+        };
+
+        match self {
+            GameResult::Resignation(color) => { write!(f, "{}+R", color_symbol(color)) }
+            GameResult::Score(color, margin) => { write!(f, "{}+{}", color_symbol(color), margin) }
+            GameResult::Draw => { write!(f, "Draw") }
+            GameResult::Void => { write!(f, "Void") }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Результат отображается в нотации SGF.
+    #[test]
+    fn display_in_sgf_notation() {
+        assert_eq!(GameResult::Resignation(Color::Black).to_string(), "B+R");
+        assert_eq!(GameResult::from_margin(-6.5).to_string(), "W+6.5");
+        assert_eq!(GameResult::from_margin(3.0).to_string(), "B+3");
+        assert_eq!(GameResult::from_margin(0.0).to_string(), "Draw");
+        assert_eq!(GameResult::Void.to_string(), "Void");
+        assert_eq!(GameResult::from_margin(0.5).winner(), Some(Color::Black));
+        assert_eq!(GameResult::Draw.winner(), None);
+    }
+}
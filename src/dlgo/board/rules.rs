@@ -0,0 +1,128 @@
+//! Правила игры в Go.
+//!
+//! Различные своды правил (китайские, японские, AGA, Тромпа-Тейлора, новозеландские)
+//! расходятся в нескольких независимых пунктах: допустим ли самозахват, как запрещаются
+//! повторения позиций (правило "ко"), как подсчитываются очки и отдает ли игрок камень
+//! противнику при пропуске хода. Набор таких пунктов задается структурой RuleSet.
+
+#![allow(dead_code)]
+
+/// Правило, запрещающее повторение позиций.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub enum KoRule {
+    /// Простое ко: нельзя сразу восстановить позицию, бывшую на доске перед последним
+    /// ходом противника.
+    Simple,
+    /// Позиционное суперко: нельзя повторить расположение камней, уже встречавшееся в игре.
+    PositionalSuperko,
+    /// Ситуационное суперко: нельзя повторить расположение камней, уже встречавшееся в игре
+    /// при той же очереди хода.
+    SituationalSuperko,
+}
+
+/// Способ подсчета очков.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub enum Scoring {
+    /// Подсчет по площади: камни на доске плюс окруженные пустые точки.
+    Area,
+    /// Подсчет по территории: окруженные пустые точки плюс пленные камни.
+    Territory,
+}
+
+/// Свод правил игры.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub struct RuleSet {
+    /// Допускается ли самозахват (ход, после которого своя цепочка снимается с доски).
+    pub suicide_allowed: bool,
+    /// Правило, запрещающее повторение позиций.
+    pub ko: KoRule,
+    /// Способ подсчета очков.
+    pub scoring: Scoring,
+    /// Правило "камня за пас": пропуская ход, игрок отдает противнику камень в пленные,
+    /// а игра заканчивается двумя пасами подряд, последний из которых делают белые.
+    pub pass_stones: bool,
+}
+
+impl RuleSet {
+    /// Китайские правила: подсчет по площади, позиционное суперко, самозахват запрещен.
+    pub fn chinese() -> Self {
+        RuleSet {
+            suicide_allowed: false,
+            ko: KoRule::PositionalSuperko,
+            scoring: Scoring::Area,
+            pass_stones: false,
+        }
+    }
+
+    /// Японские правила: подсчет по территории, простое ко, самозахват запрещен.
+    pub fn japanese() -> Self {
+        RuleSet {
+            suicide_allowed: false,
+            ko: KoRule::Simple,
+            scoring: Scoring::Territory,
+            pass_stones: false,
+        }
+    }
+
+    /// Правила Американской ассоциации Go (AGA): подсчет по площади, ситуационное суперко,
+    /// самозахват запрещен, камень за пас.
+    pub fn aga() -> Self {
+        RuleSet {
+            suicide_allowed: false,
+            ko: KoRule::SituationalSuperko,
+            scoring: Scoring::Area,
+            pass_stones: true,
+        }
+    }
+
+    /// Правила Тромпа-Тейлора: подсчет по площади, позиционное суперко, самозахват разрешен.
+    pub fn tromp_taylor() -> Self {
+        RuleSet {
+            suicide_allowed: true,
+            ko: KoRule::PositionalSuperko,
+            scoring: Scoring::Area,
+            pass_stones: false,
+        }
+    }
+
+    /// Новозеландские правила: подсчет по площади, ситуационное суперко, самозахват
+    /// разрешен.
+    pub fn new_zealand() -> Self {
+        RuleSet {
+            suicide_allowed: true,
+            ko: KoRule::SituationalSuperko,
+            scoring: Scoring::Area,
+            pass_stones: false,
+        }
+    }
+
+    /// Коми (очки, добавляемые белым за право первого хода черных), принятое для способа
+    /// подсчета очков этих правил: 7.5 при подсчете по площади и 6.5 по территории.
+    pub fn default_komi(&self) -> f32 {
+        match self.scoring {
+            Scoring::Area => { 7.5 }
+            Scoring::Territory => { 6.5 }
+        }
+    }
+
+    /// Коми для партии с форой.
+    ///
+    /// # Arguments
+    ///
+    /// * `stones`: Число камней форы (0 - игра без форы).
+    ///
+    /// Returns: f32 - коми этих правил без форы и 0.5 с форой, когда первыми ходят белые
+    pub fn handicap_komi(&self, stones: usize) -> f32 {
+        if stones == 0 {
+            self.default_komi()
+        } else {
+            0.5
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Подсчет очков завершенной партии.
+//!
+//! Пустые области доски находятся заливкой. Область, граничащая с камнями только одного
+//! цвета, является территорией этого цвета, остальные пустые точки нейтральны (даме). По
+//! правилам Тромпа-Тейлора (подсчет по площади) очки игрока - это его камни на доске плюс
+//! его территория.
+
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use crate::dlgo::board::goboard::Board;
+use crate::dlgo::gotypes::{Color, Point};
+
+/// Принадлежность точки доски после подсчета.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub enum PointStatus {
+    BlackStone,
+    WhiteStone,
+    BlackTerritory,
+    WhiteTerritory,
+    // Нейтральная пустая точка, граничащая с камнями обоих цветов (или ни с какими).
+    Dame,
+}
+
+impl PointStatus {
+    /// Цвет игрока, которому засчитывается точка при подсчете по площади.
+    pub fn owner(&self) -> Option<Color> {
+        match self {
+            PointStatus::BlackStone | PointStatus::BlackTerritory => { Some(Color::Black) }
+            PointStatus::WhiteStone | PointStatus::WhiteTerritory => { Some(Color::White) }
+            PointStatus::Dame => { None }
+        }
+    }
+}
+
+/// Результат подсчета: итоги по каждому цвету и карта принадлежности точек.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Territory {
+    pub black_stones: usize,
+    pub white_stones: usize,
+    pub black_territory: usize,
+    pub white_territory: usize,
+    pub dame: usize,
+    // Карта принадлежности точек: строки доски, начиная с первой.
+    map: Vec<Vec<PointStatus>>,
+}
+
+impl Territory {
+    /// Очки черных при подсчете по площади: камни и территория.
+    pub fn black_area(&self) -> usize {
+        self.black_stones + self.black_territory
+    }
+
+    /// Очки белых при подсчете по площади: камни и территория.
+    pub fn white_area(&self) -> usize {
+        self.white_stones + self.white_territory
+    }
+
+    /// Принадлежность точки доски.
+    ///
+    /// # Arguments
+    ///
+    /// * `point`: Точка на доске.
+    ///
+    /// Returns: PointStatus
+    pub fn status(&self, point: &Point) -> PointStatus {
+        self.map[point.row - 1][point.col - 1]
+    }
+
+    /// Цвет игрока, которому принадлежит точка доски, или None для нейтральной точки.
+    ///
+    /// # Arguments
+    ///
+    /// * `point`: Точка на доске.
+    ///
+    /// Returns: Option<Color>
+    pub fn owner(&self, point: &Point) -> Option<Color> {
+        self.status(point).owner()
+    }
+
+    /// Карта принадлежности точек (строки доски, начиная с первой).
+    pub fn ownership_map(&self) -> &Vec<Vec<PointStatus>> {
+        &self.map
+    }
+}
+
+/// Подсчет камней и территории на доске.
+///
+/// # Arguments
+///
+/// * `board`: Доска в конечной позиции партии.
+///
+/// Returns: Territory
+pub fn evaluate_territory(board: &Board) -> Territory {
+    let mut map = vec![vec![PointStatus::Dame; board.num_cols()]; board.num_rows()];
+    let mut visited: HashSet<Point> = HashSet::new();
+
+    let mut territory = Territory {
+        black_stones: 0,
+        white_stones: 0,
+        black_territory: 0,
+        white_territory: 0,
+        dame: 0,
+        map: Vec::new(),
+    };
+
+    for row in 1..=board.num_rows() {
+        for col in 1..=board.num_cols() {
+            let point = Point::new(row, col);
+
+            match board.get_color(&point) {
+                Some(Color::Black) => {
+                    territory.black_stones += 1;
+                    map[row - 1][col - 1] = PointStatus::BlackStone;
+                }
+                Some(Color::White) => {
+                    territory.white_stones += 1;
+                    map[row - 1][col - 1] = PointStatus::WhiteStone;
+                }
+                None => {
+                    if visited.contains(&point) {
+                        continue;
+                    }
+
+                    let (region, borders) = collect_region(board, point, &mut visited);
+
+                    // Область принадлежит цвету, если граничит только с его камнями.
+                    let touches_black = borders.contains(&Color::Black);
+                    let touches_white = borders.contains(&Color::White);
+
+                    let status = match (touches_black, touches_white) {
+                        (true, false) => {
+                            territory.black_territory += region.len();
+                            PointStatus::BlackTerritory
+                        }
+                        (false, true) => {
+                            territory.white_territory += region.len();
+                            PointStatus::WhiteTerritory
+                        }
+                        _ => {
+                            territory.dame += region.len();
+                            PointStatus::Dame
+                        }
+                    };
+
+                    for p in region {
+                        map[p.row - 1][p.col - 1] = status;
+                    }
+                }
+            }
+        }
+    }
+
+    territory.map = map;
+    territory
+}
+
+/// Заливка пустой области, содержащей переданную точку.
+///
+/// # Arguments
+///
+/// * `board`: Доска.
+/// * `start`: Пустая точка области.
+/// * `visited`: Уже обойденные пустые точки (дополняется точками области).
+///
+/// Returns: (Vec<Point>, HashSet<Color>) - точки области и цвета камней на ее границе
+fn collect_region(
+    board: &Board,
+    start: Point,
+    visited: &mut HashSet<Point>
+) -> (Vec<Point>, HashSet<Color>) {
+    let mut region: Vec<Point> = Vec::new();
+    let mut borders: HashSet<Color> = HashSet::new();
+    let mut stack = vec![start];
+    visited.insert(start);
+
+    while let Some(current) = stack.pop() {
+        region.push(current);
+
+        for &neighbor in board.geometry().neighbors(&current) {
+            match board.get_color(&neighbor) {
+                Some(color) => { borders.insert(color); }
+                None => {
+                    if visited.insert(neighbor) {
+                        stack.push(neighbor);
+                    }
+                }
+            }
+        }
+    }
+
+    (region, borders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Подсчет по площади: стены разделяют доску на территории, точки между стенами
+    /// противников нейтральны.
+    #[test]
+    fn area_scoring_with_ownership_map() {
+        let mut board = Board::new(5, 5);
+        for row in 1..=5 {
+            board.place_stone((Color::Black, Point::new(row, 2))).unwrap();
+            board.place_stone((Color::White, Point::new(row, 4))).unwrap();
+        }
+
+        let territory = evaluate_territory(&board);
+
+        assert_eq!(territory.black_stones, 5);
+        assert_eq!(territory.white_stones, 5);
+        assert_eq!(territory.black_territory, 5);
+        assert_eq!(territory.white_territory, 5);
+        assert_eq!(territory.dame, 5);
+        assert_eq!(territory.black_area(), 10);
+        assert_eq!(territory.white_area(), 10);
+
+        assert_eq!(territory.status(&Point::new(1, 1)), PointStatus::BlackTerritory);
+        assert_eq!(territory.status(&Point::new(3, 2)), PointStatus::BlackStone);
+        assert_eq!(territory.status(&Point::new(3, 3)), PointStatus::Dame);
+        assert_eq!(territory.owner(&Point::new(5, 5)), Some(Color::White));
+        assert_eq!(territory.owner(&Point::new(2, 3)), None);
+        assert_eq!(territory.ownership_map().len(), 5);
+    }
+
+    /// На пустой доске все точки нейтральны.
+    #[test]
+    fn empty_board_is_dame() {
+        let territory = evaluate_territory(&Board::new(9, 9));
+
+        assert_eq!(territory.dame, 81);
+        assert_eq!(territory.black_area(), 0);
+        assert_eq!(territory.white_area(), 0);
+    }
+}
@@ -0,0 +1,204 @@
+//! Обнаружение секи (взаимной жизни).
+//!
+//! Секи - положение, в котором цепочки черных и белых разделяют степени свободы, и ни одна
+//! из сторон не может безопасно их заполнить: заполнение ставит собственную цепочку в атари
+//! (или является самозахватом), после чего противник ее захватывает. Случайные доигровки
+//! не должны заполнять такие общие степени свободы (точки секи). При подсчете по территории
+//! глаза цепочек в секи не засчитываются игроку (см. Game::score).
+//!
+//! Глаза распознаются только как отдельные точки (Board::is_point_an_eye), поэтому секи,
+//! в котором у цепочки глаз из двух и более точек, не обнаруживается.
+
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use crate::dlgo::board::goboard::Board;
+use crate::dlgo::board::gostring::GoString;
+use crate::dlgo::gotypes::{Color, Point};
+
+/// Найденные секи: цепочки в секи, их общие степени свободы и глаза.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Seki {
+    strings: Vec<GoString>,
+    liberties: HashSet<Point>,
+    eyes: HashSet<Point>,
+}
+
+impl Seki {
+    /// Цепочки камней обоих цветов, находящиеся в секи.
+    pub fn strings(&self) -> &[GoString] {
+        &self.strings
+    }
+
+    /// Общие степени свободы цепочек в секи (нейтральные точки).
+    pub fn liberties(&self) -> &HashSet<Point> {
+        &self.liberties
+    }
+
+    /// Собственные глаза цепочек в секи (степени свободы, не являющиеся точками секи).
+    pub fn eyes(&self) -> &HashSet<Point> {
+        &self.eyes
+    }
+
+    /// Показывает, принадлежит ли камень в точке цепочке в секи.
+    pub fn contains(&self, point: &Point) -> bool {
+        self.strings.iter().any(|string| string.get_stones2().contains(point))
+    }
+
+    /// Показывает, нет ли на доске секи.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+/// Показывает, является ли пустая точка точкой секи-кандидатом: она соседствует с цепочками
+/// обоих цветов, и ход в нее любого игрока - самозахват или самоатари.
+///
+/// # Arguments
+///
+/// * `board`: Доска.
+/// * `point`: Точка на доске.
+///
+/// Returns: bool
+pub fn is_seki_point(board: &Board, point: &Point) -> bool {
+    if !board.is_on_grid(point) || board.get_color(point).is_some() {
+        return false;
+    }
+
+    let neighbors: Vec<Color> = board.geometry().neighbors(point).iter()
+        .filter_map(|neighbor| board.get_color(neighbor))
+        .collect();
+
+    [Color::Black, Color::White].iter().all(|&color| {
+        let stone = (color, *point);
+
+        neighbors.contains(&color)
+            && (board.is_self_capture(stone) || board.is_self_atari(stone))
+    })
+}
+
+/// Находит секи на доске. Цепочка находится в секи, если она касается точки секи, а все ее
+/// степени свободы - точки секи или ее собственные глаза. Точка секи остается таковой,
+/// только если все соседние цепочки находятся в секи.
+///
+/// # Arguments
+///
+/// * `board`: Доска.
+///
+/// Returns: Seki
+pub fn find_seki(board: &Board) -> Seki {
+    let mut points: HashSet<Point> = HashSet::new();
+    for row in 1..=board.num_rows() {
+        for col in 1..=board.num_cols() {
+            let point = Point::new(row, col);
+            if is_seki_point(board, &point) {
+                points.insert(point);
+            }
+        }
+    }
+
+    // Цепочки, касающиеся точек секи (без повторов).
+    let mut strings: Vec<GoString> = Vec::new();
+    for point in &points {
+        for &neighbor in board.geometry().neighbors(point) {
+            if board.get_color(&neighbor).is_some()
+                && !strings.iter().any(|string| string.get_stones2().contains(&neighbor))
+            {
+                strings.push(board.get_go_string(&neighbor).unwrap());
+            }
+        }
+    }
+
+    loop {
+        let before = (strings.len(), points.len());
+
+        strings.retain(|string| {
+            string.get_liberties().iter().all(|liberty| {
+                points.contains(liberty)
+                    || board.is_point_an_eye(liberty, string.get_color()).is_some()
+            })
+        });
+
+        points.retain(|point| {
+            board.geometry().neighbors(point).iter()
+                .filter(|neighbor| board.get_color(neighbor).is_some())
+                .all(|neighbor| strings.iter().any(|string| string.get_stones2().contains(neighbor)))
+        });
+
+        if (strings.len(), points.len()) == before {
+            break;
+        }
+    }
+
+    let eyes = strings.iter()
+        .flat_map(|string| string.get_liberties())
+        .filter(|liberty| !points.contains(liberty))
+        .collect();
+
+    Seki {
+        strings,
+        liberties: points,
+        eyes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlgo::board::fixtures;
+
+    /// Общие степени свободы внутренних цепочек - точки секи.
+    #[test]
+    fn shared_liberties_in_seki() {
+        let board = Board::from_diagram(fixtures::SEKI).unwrap();
+
+        let seki = find_seki(&board);
+
+        assert_eq!(seki.strings().len(), 2);
+        assert!(seki.contains(&Point::new(2, 2)) && seki.contains(&Point::new(1, 1)));
+        assert!(!seki.contains(&Point::new(5, 5)));
+        assert_eq!(seki.liberties().len(), 2);
+        assert!(board.is_self_atari((Color::Black, Point::new(1, 2))));
+        assert!(board.is_self_atari((Color::White, Point::new(1, 3))));
+        assert!(!board.is_self_atari((Color::Black, Point::new(7, 7))));
+        assert!(seki.eyes().is_empty());
+    }
+
+    /// Цепочки с одним глазом и общей степенью свободы находятся в секи, глаза
+    /// запоминаются.
+    #[test]
+    fn seki_with_eyes() {
+        let board = Board::from_diagram(fixtures::SEKI_WITH_EYES).unwrap();
+
+        let seki = find_seki(&board);
+
+        assert_eq!(seki.strings().len(), 2);
+        assert_eq!(seki.liberties(), &HashSet::from([Point::new(1, 3)]));
+        assert_eq!(seki.eyes(), &HashSet::from([Point::new(1, 1), Point::new(1, 5)]));
+    }
+
+    /// Семеай: общую степень свободы может заполнить любая сторона, захватив цепочку
+    /// противника, поэтому это не секи.
+    #[test]
+    fn no_seki_in_capturing_race() {
+        let board = Board::from_diagram("
+            . O X
+            X O X
+            X X X
+        ").unwrap();
+
+        assert!(!is_seki_point(&board, &Point::new(1, 1)));
+        assert!(find_seki(&board).is_empty());
+    }
+
+    /// Если одна из сторон может заполнить общую степень свободы без самоатари, секи нет.
+    #[test]
+    fn no_seki_with_outside_liberty() {
+        let mut board = Board::new(9, 9);
+        board.place_stone((Color::Black, Point::new(5, 5))).unwrap();
+        board.place_stone((Color::White, Point::new(5, 6))).unwrap();
+
+        assert!(find_seki(&board).is_empty());
+    }
+}
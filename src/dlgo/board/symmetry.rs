@@ -0,0 +1,300 @@
+//! Симметрии доски: четыре поворота и четыре отражения (группа диэдра).
+//!
+//! Позиции, переходящие друг в друга при повороте или отражении доски, равноценны.
+//! Преобразования применяются к точкам, ходам, доскам и целым партиям (для расширения
+//! обучающих данных, библиотек дебютов и таблиц транспозиций), а канонический вид доски
+//! выбирает из восьми симметричных позиций одну. Повороты на 90 и 270 градусов и
+//! отражения относительно диагоналей меняют местами число строк и колонок прямоугольной
+//! доски.
+
+#![allow(dead_code)]
+
+use crate::dlgo::board::game::Game;
+use crate::dlgo::board::goboard::Board;
+use crate::dlgo::error::IllegalMove;
+use crate::dlgo::gotypes::{Move, Point};
+
+/// Преобразование симметрии доски.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq, Hash)]
+#[derive(Debug)]
+pub enum Symmetry {
+    /// Тождественное преобразование.
+    Identity,
+    /// Поворот на 90 градусов по часовой стрелке.
+    Rotate90,
+    /// Поворот на 180 градусов.
+    Rotate180,
+    /// Поворот на 270 градусов по часовой стрелке.
+    Rotate270,
+    /// Отражение слева направо (колонки в обратном порядке).
+    FlipHorizontal,
+    /// Отражение сверху вниз (строки в обратном порядке).
+    FlipVertical,
+    /// Отражение относительно главной диагонали (строки становятся колонками).
+    Transpose,
+    /// Отражение относительно побочной диагонали.
+    AntiTranspose,
+}
+
+impl Symmetry {
+    /// Все восемь преобразований, начиная с тождественного.
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// Обратное преобразование.
+    pub fn inverse(&self) -> Symmetry {
+        match self {
+            Symmetry::Rotate90 => { Symmetry::Rotate270 }
+            Symmetry::Rotate270 => { Symmetry::Rotate90 }
+            // Остальные преобразования обратны сами себе.
+            symmetry => { *symmetry }
+        }
+    }
+
+    /// Показывает, меняет ли преобразование местами строки и колонки.
+    pub fn swaps_axes(&self) -> bool {
+        matches!(
+            self,
+            Symmetry::Rotate90 | Symmetry::Rotate270 | Symmetry::Transpose | Symmetry::AntiTranspose
+        )
+    }
+
+    /// Размер доски после преобразования.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_rows`: Число строк исходной доски.
+    /// * `num_cols`: Число колонок исходной доски.
+    ///
+    /// Returns: (usize, usize) - число строк и колонок преобразованной доски
+    pub fn apply_size(&self, num_rows: usize, num_cols: usize) -> (usize, usize) {
+        if self.swaps_axes() {
+            (num_cols, num_rows)
+        } else {
+            (num_rows, num_cols)
+        }
+    }
+
+    /// Преобразует точку доски.
+    ///
+    /// # Arguments
+    ///
+    /// * `point`: Точка исходной доски.
+    /// * `num_rows`: Число строк исходной доски.
+    /// * `num_cols`: Число колонок исходной доски.
+    ///
+    /// Returns: Point - точка преобразованной доски
+    pub fn apply_point(&self, point: &Point, num_rows: usize, num_cols: usize) -> Point {
+        let (row, col) = (point.row, point.col);
+        let (flipped_row, flipped_col) = (num_rows + 1 - row, num_cols + 1 - col);
+
+        match self {
+            Symmetry::Identity => { Point::new(row, col) }
+            Symmetry::Rotate90 => { Point::new(col, flipped_row) }
+            Symmetry::Rotate180 => { Point::new(flipped_row, flipped_col) }
+            Symmetry::Rotate270 => { Point::new(flipped_col, row) }
+            Symmetry::FlipHorizontal => { Point::new(row, flipped_col) }
+            Symmetry::FlipVertical => { Point::new(flipped_row, col) }
+            Symmetry::Transpose => { Point::new(col, row) }
+            Symmetry::AntiTranspose => { Point::new(flipped_col, flipped_row) }
+        }
+    }
+
+    /// Преобразует ход: точка камня преобразуется, пропуск хода и выход из игры не меняются.
+    ///
+    /// # Arguments
+    ///
+    /// * `player_move`: Ход на исходной доске.
+    /// * `num_rows`: Число строк исходной доски.
+    /// * `num_cols`: Число колонок исходной доски.
+    ///
+    /// Returns: Move
+    pub fn apply_move(&self, player_move: Move, num_rows: usize, num_cols: usize) -> Move {
+        match player_move {
+            Move::Play((color, point)) => {
+                Move::Play((color, self.apply_point(&point, num_rows, num_cols)))
+            }
+            _ => { player_move }
+        }
+    }
+
+    /// Преобразует расположение камней на доске (стек отмены ходов не переносится).
+    ///
+    /// # Arguments
+    ///
+    /// * `board`: Исходная доска.
+    ///
+    /// Returns: Board
+    pub fn apply_board(&self, board: &Board) -> Board {
+        let (num_rows, num_cols) = (board.num_rows(), board.num_cols());
+        let (new_rows, new_cols) = self.apply_size(num_rows, num_cols);
+        let mut transformed = Board::new(new_rows, new_cols);
+
+        for row in 1..=num_rows {
+            for col in 1..=num_cols {
+                let point = Point::new(row, col);
+
+                if let Some(color) = board.get_color(&point) {
+                    // Симметрия сохраняет степени свободы, поэтому камни ничего не захватывают.
+                    let point = self.apply_point(&point, num_rows, num_cols);
+                    transformed.place_stone((color, point)).unwrap();
+                }
+            }
+        }
+
+        transformed
+    }
+
+    /// Преобразует запись партии: камни форы и все сделанные ходы переигрываются на
+    /// преобразованной доске с теми же правилами и коми, переносятся и отметки мертвых
+    /// камней. Отмененные ходы (стек redo) не переносятся.
+    ///
+    /// # Arguments
+    ///
+    /// * `game`: Исходная партия.
+    ///
+    /// Returns: Result<Game, IllegalMove>
+    pub fn apply_game(&self, game: &Game) -> Result<Game, IllegalMove> {
+        let (num_rows, num_cols) = (game.num_rows(), game.num_cols());
+        let (new_rows, new_cols) = self.apply_size(num_rows, num_cols);
+
+        let handicap = game.handicap().iter()
+            .map(|point| self.apply_point(point, num_rows, num_cols))
+            .collect();
+
+        let mut transformed = Game::with_handicap_stones(new_rows, new_cols, *game.rules(), game.komi(), handicap);
+
+        for player_move in game.moves() {
+            transformed.apply_move(self.apply_move(player_move, num_rows, num_cols))?;
+        }
+
+        for point in game.dead_stones() {
+            let point = self.apply_point(point, num_rows, num_cols);
+
+            if !transformed.is_dead(&point) {
+                // Отметка переключается для всей цепочки, поэтому повторно не выполняется.
+                transformed.toggle_dead_string(&point)
+                    .expect("Отметка мертвых камней переносится в завершенную партию");
+            }
+        }
+
+        Ok(transformed)
+    }
+}
+
+/// Канонический вид доски: из восьми симметричных позиций выбирается наименьшая (по
+/// размеру, затем по содержимому точек построчно: пусто < черный < белый). Симметричные
+/// позиции имеют один и тот же канонический вид.
+///
+/// # Arguments
+///
+/// * `board`: Доска.
+///
+/// Returns: (Board, Symmetry) - канонический вид доски и преобразование, приводящее к нему
+pub fn canonical_form(board: &Board) -> (Board, Symmetry) {
+    Symmetry::ALL.iter()
+        .map(|&symmetry| {
+            let transformed = symmetry.apply_board(board);
+            (ordering_key(&transformed), transformed, symmetry)
+        })
+        .min_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, transformed, symmetry)| (transformed, symmetry))
+        .unwrap()
+}
+
+/// Ключ сравнения досок для выбора канонического вида.
+fn ordering_key(board: &Board) -> (usize, usize, Vec<u8>) {
+    let mut points = Vec::with_capacity(board.num_rows() * board.num_cols());
+
+    for row in 1..=board.num_rows() {
+        for col in 1..=board.num_cols() {
+            points.push(match board.get_color(&Point::new(row, col)) {
+                None => { 0 }
+                Some(color) => { color as u8 + 1 }
+            });
+        }
+    }
+
+    (board.num_rows(), board.num_cols(), points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlgo::board::rules::RuleSet;
+    use crate::dlgo::gotypes::Color;
+
+    /// Преобразования точек прямоугольной доски и обратные им преобразования.
+    #[test]
+    fn point_transforms_and_inverses() {
+        let point = Point::new(1, 2);
+
+        // Доска 3x4: строка 1, колонка 2.
+        assert_eq!(Symmetry::Rotate90.apply_point(&point, 3, 4), Point::new(2, 3));
+        assert_eq!(Symmetry::Rotate180.apply_point(&point, 3, 4), Point::new(3, 3));
+        assert_eq!(Symmetry::Rotate270.apply_point(&point, 3, 4), Point::new(3, 1));
+        assert_eq!(Symmetry::FlipHorizontal.apply_point(&point, 3, 4), Point::new(1, 3));
+        assert_eq!(Symmetry::FlipVertical.apply_point(&point, 3, 4), Point::new(3, 2));
+        assert_eq!(Symmetry::Transpose.apply_point(&point, 3, 4), Point::new(2, 1));
+        assert_eq!(Symmetry::AntiTranspose.apply_point(&point, 3, 4), Point::new(3, 3));
+
+        for symmetry in Symmetry::ALL {
+            let (rows, cols) = symmetry.apply_size(3, 4);
+            let transformed = symmetry.apply_point(&point, 3, 4);
+
+            assert!(transformed.row <= rows && transformed.col <= cols);
+            assert_eq!(symmetry.inverse().apply_point(&transformed, rows, cols), point);
+        }
+
+        assert_eq!(Symmetry::Rotate90.apply_move(Move::Pass(Color::White), 3, 4), Move::Pass(Color::White));
+    }
+
+    /// Повернутая доска и все ее симметрии имеют общий канонический вид.
+    #[test]
+    fn board_transforms_and_canonical_form() {
+        let board = Board::from_diagram("
+            X O . .
+            . X . .
+            . . . O
+        ").unwrap();
+
+        let rotated = Symmetry::Rotate90.apply_board(&board);
+        assert_eq!(rotated.to_string(), ". . X\n. X O\n. . .\nO . .\n");
+
+        let (canonical, symmetry) = canonical_form(&board);
+        assert!(symmetry.apply_board(&board) == canonical);
+
+        for symmetry in Symmetry::ALL {
+            let (other, _) = canonical_form(&symmetry.apply_board(&board));
+            assert!(other == canonical);
+        }
+    }
+
+    /// Преобразованная партия повторяет ходы, захваты и коми исходной.
+    #[test]
+    fn game_record_transform() {
+        let mut game = Game::new(9, RuleSet::japanese());
+        game.set_komi(0.5);
+        for (row, col) in [(1, 2), (1, 1), (5, 5), (9, 9), (2, 1)] {
+            let color = game.state().player_color();
+            game.apply_move(Move::Play((color, Point::new(row, col)))).unwrap();
+        }
+
+        let transformed = Symmetry::AntiTranspose.apply_game(&game).unwrap();
+
+        assert_eq!(transformed.komi(), 0.5);
+        assert_eq!(transformed.move_number(), game.move_number());
+        assert_eq!(transformed.prisoners(Color::Black), 1);
+        assert!(transformed.state().board() == &Symmetry::AntiTranspose.apply_board(game.state().board()));
+        assert_eq!(transformed.moves().next(), Some(Move::Play((Color::Black, Point::new(8, 9)))));
+    }
+}
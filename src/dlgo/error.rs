@@ -1,5 +1,6 @@
 use std::error::Error;
 use std::fmt;
+use crate::dlgo::gotypes::{Color, Point, Stone};
 
 /// Исправимые ошибки.
 #[derive(Debug, Clone)]
@@ -21,4 +22,63 @@ impl fmt::Display for FatalError {
     }
 }
 
-impl Error for FatalError {}
\ No newline at end of file
+impl Error for FatalError {}
+
+/// Причина, по которой ход не может быть сделан. В отличие от FatalError варианты позволяют
+/// вызывающему коду (GTP, интерфейсу, ботам) различать ошибки и получать точку и цвет хода.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub enum IllegalMove {
+    /// Игра завершена.
+    GameOver,
+    /// Ход сделан не в свою очередь.
+    WrongColor {
+        /// Цвет игрока, который должен сделать ход.
+        expected: Color,
+        /// Цвет игрока, сделавшего ход.
+        played: Color,
+    },
+    /// Точка находится за границами сетки доски.
+    OffBoard(Point),
+    /// Точка уже занята камнем.
+    Occupied(Point),
+    /// Ход приводит к самозахвату, а правила его не допускают.
+    SelfCapture(Stone),
+    /// Ход повторяет позицию и нарушает правило "ко" (или суперко).
+    Ko(Stone),
+}
+
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IllegalMove::GameOver => {
+                write!(f, "Игра завершена! Нельзя делать ходы в завершенной игре.")
+            }
+            IllegalMove::WrongColor { expected, played } => {
+                write!(f, "Ход должен делать игрок, играющий камнями цвета: {} (а не {})", expected, played)
+            }
+            IllegalMove::OffBoard(point) => {
+                write!(f, "Точка ({}, {}) находится за границами сетки доски", point.row, point.col)
+            }
+            IllegalMove::Occupied(point) => {
+                write!(f, "В точке ({}, {}) уже находится камень", point.row, point.col)
+            }
+            IllegalMove::SelfCapture((color, point)) => {
+                write!(f, "Ход в точку ({}, {}) - самозахват ({})", point.row, point.col, color)
+            }
+            IllegalMove::Ko((color, point)) => {
+                write!(f, "Ход в точку ({}, {}) нарушает правило ко ({})", point.row, point.col, color)
+            }
+        }
+    }
+}
+
+impl Error for IllegalMove {}
+
+/// Недопустимый ход там, где ожидается FatalError (например, при ходе в дереве партии).
+impl From<IllegalMove> for FatalError {
+    fn from(err: IllegalMove) -> Self {
+        FatalError::new(err.to_string())
+    }
+}
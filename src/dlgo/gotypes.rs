@@ -7,7 +7,7 @@ use std::fmt::Formatter;
 
 /// Тип, представляющий цвет камня в игре.
 #[derive(Copy, Clone)]
-#[derive(Eq, PartialEq)]
+#[derive(Eq, PartialEq, Hash)]
 #[derive(Debug)]
 pub enum Color {
     Black = 0,
@@ -50,12 +50,14 @@ impl Point {
         }
     }
 
+    /// Четыре соседние точки без учета границ доски (точки нулевой линии переходят через
+    /// ноль, а не вызывают переполнение). Соседи в пределах доски - Geometry::neighbors.
     pub fn neighbors(&self) -> [Point; 4] {
         [
-            Point{ row: self.col, col: self.row - 1, },
-            Point{ row: self.col, col: self.row + 1, },
-            Point{ row: self.col - 1, col: self.row, },
-            Point{ row: self.col + 1, col: self.row, },
+            Point{ row: self.row, col: self.col.wrapping_sub(1), },
+            Point{ row: self.row, col: self.col + 1, },
+            Point{ row: self.row.wrapping_sub(1), col: self.col, },
+            Point{ row: self.row + 1, col: self.col, },
         ]
     }
 }
@@ -66,12 +68,24 @@ pub type Stone = (Color, Point);
 /// Тип, представляющий ход игрока. Возможны 3 действиями:
 /// размещение камня на доске (play), пропуск хода (pass),
 /// и выход из игры (resign).
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
 pub enum Move {
     Play(Stone),
     Pass(Color),
     Resign(Color)
 }
 
+impl Move {
+    /// Возвращает цвет игрока, который делает ход.
+    pub fn get_color(&self) -> Color {
+        match self {
+            Move::Play((color, _)) | Move::Pass(color) | Move::Resign(color) => { *color }
+        }
+    }
+}
+
 impl std::fmt::Display for Move {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -114,12 +128,13 @@ pub enum DisplayPoint {
     Empty,
     BlackStone,
     WhiteStone,
+    // Камни, отмеченные мертвыми после окончания партии.
+    DeadBlackStone,
+    DeadWhiteStone,
 
     // На будущее...
     //BlackLiberty,
     //WhiteLiberty,
-    //DeadBlackStone,
-    //DeadWhiteStone,
 }
 
 /// Карта текущего состояния игрового поля. Используется для отображения игры на экране.
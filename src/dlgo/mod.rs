@@ -2,3 +2,4 @@ pub mod gotypes;
 pub mod board;
 pub mod agent;
 pub mod error;
+pub mod zobrist;
@@ -0,0 +1,46 @@
+//! Хеширование Зобриста для позиций на доске.
+//!
+//! Каждому камню (цвет и точка) сопоставляется псевдослучайное 64-битное число. Хеш доски
+//! равен XOR чисел всех стоящих на ней камней, поэтому при размещении или снятии камня хеш
+//! обновляется за O(1): достаточно применить XOR с числом этого камня.
+
+use crate::dlgo::gotypes::{Color, Stone};
+
+/// Хеш пустой доски.
+pub const EMPTY_BOARD: u64 = 0;
+
+/// Начальное значение генератора (произвольная константа, фиксирует хеши между запусками).
+const SEED: u64 = 0x5DEE_CE66_D1CE_4E5B;
+
+/// Возвращает хеш-код камня.
+///
+/// Вместо таблицы, ограничивающей размер доски, число вычисляется функцией перемешивания
+/// SplitMix64 от координат точки и цвета. Результат детерминирован и не зависит от размера
+/// доски.
+///
+/// # Arguments
+///
+/// * `stone`: Камень (цвет и точка на доске).
+///
+/// Returns: u64
+pub fn hash_code(stone: &Stone) -> u64 {
+    let (color, point) = stone;
+
+    let color_idx: u64 = match color {
+        Color::Black => { 0 }
+        Color::White => { 1 }
+    };
+
+    let key = ((point.row as u64) << 33) | ((point.col as u64) << 1) | color_idx;
+
+    splitmix64(SEED ^ key)
+}
+
+/// Функция перемешивания SplitMix64.
+pub(crate) fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+    z ^ (z >> 31)
+}
@@ -5,26 +5,28 @@ use std::thread::spawn;
 use crate::display::ScreenType::{Console, Tetra};
 use crate::dlgo::gotypes::{Move, Color, Stone, Point, DisplayState};
 use crate::dlgo::board::game::Game;
+use crate::dlgo::board::rules::RuleSet;
 
 mod dlgo;
 mod display;
 
 fn main() {
     // Сценарий запуска
-    let board_size: usize = 19;
+    let (num_rows, num_cols): (usize, usize) = (19, 19);
 
-    let mut game = Game::new(board_size);
+    let mut game = Game::new_rectangular(num_rows, num_cols, RuleSet::chinese()).unwrap();
 
     // Канал для пересылки сообщений о состоянии игры.
     let (sender, receiver) = channel::<DisplayState>();
 
 
     // Запуск отображения игры в отдельном потоке.
-    // Оператор move копирует (копируемый тип) board_size в замыкание.
+    // Оператор move копирует (копируемый тип) num_rows и num_cols в замыкание.
     let handler = spawn(move || {
         display::launch(
             Tetra,
-            board_size,
+            num_rows,
+            num_cols,
             receiver,
             "./resources",
             "Игра Go!"